@@ -0,0 +1,40 @@
+use bevy::math::{transform_points, transform_points_vec3a, Mat4, Quat, Vec3, Vec3A};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+criterion_group!(benches, bench_transform_points);
+criterion_main!(benches);
+
+fn bench_transform_points(c: &mut Criterion) {
+    let matrix = Mat4::from_scale_rotation_translation(
+        Vec3::new(1.0, 2.0, 3.0),
+        Quat::from_rotation_y(0.5),
+        Vec3::new(-1.0, 4.0, 0.5),
+    );
+
+    let mut group = c.benchmark_group("transform_points");
+    for count in &[100, 10_000, 1_000_000] {
+        let points = (0..*count)
+            .map(|i| Vec3::splat(i as f32))
+            .collect::<Vec<_>>();
+        let mut transformed = Vec::with_capacity(*count);
+        group.bench_with_input(BenchmarkId::new("vec3", count), count, |b, _| {
+            b.iter(|| {
+                transformed.clear();
+                transformed.extend(transform_points(black_box(&matrix), points.iter().copied()));
+            })
+        });
+
+        let points = points.into_iter().map(Vec3A::from).collect::<Vec<_>>();
+        let mut transformed = Vec::with_capacity(*count);
+        group.bench_with_input(BenchmarkId::new("vec3a", count), count, |b, _| {
+            b.iter(|| {
+                transformed.clear();
+                transformed.extend(transform_points_vec3a(
+                    black_box(&matrix),
+                    points.iter().copied(),
+                ));
+            })
+        });
+    }
+    group.finish();
+}
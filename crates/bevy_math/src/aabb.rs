@@ -0,0 +1,186 @@
+use crate::{Mat4, Vec3, Vec3A};
+
+/// An axis-aligned bounding box, defined by its center and half extents
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+}
+
+impl Aabb {
+    pub fn new(center: Vec3, half_extents: Vec3) -> Self {
+        Aabb {
+            center,
+            half_extents,
+        }
+    }
+
+    /// Creates an `Aabb` spanning the two given corners
+    pub fn from_min_max(min: Vec3, max: Vec3) -> Self {
+        Aabb {
+            center: 0.5 * (max + min),
+            half_extents: 0.5 * (max - min),
+        }
+    }
+
+    /// The corner of the box with the smallest coordinates
+    pub fn min(&self) -> Vec3 {
+        self.center - self.half_extents
+    }
+
+    /// The corner of the box with the largest coordinates
+    pub fn max(&self) -> Vec3 {
+        self.center + self.half_extents
+    }
+
+    /// Returns the smallest `Aabb` containing both `self` and `other`
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        Aabb::from_min_max(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    /// Returns the smallest `Aabb` containing this box once transformed by the affine `matrix`.
+    ///
+    /// The result stays tight under rotation: each axis of the new box is as wide as the
+    /// rotated and scaled box projected on it, rather than the bounds of a looser sphere.
+    pub fn transformed_by(&self, matrix: &Mat4) -> Aabb {
+        let center = matrix.transform_point3(self.center);
+        let half_extents = matrix.x_axis.truncate().abs() * self.half_extents.x
+            + matrix.y_axis.truncate().abs() * self.half_extents.y
+            + matrix.z_axis.truncate().abs() * self.half_extents.z;
+        Aabb {
+            center,
+            half_extents,
+        }
+    }
+
+    /// Returns `true` if `point` is inside the box, or on its surface
+    pub fn contains_point(&self, point: Vec3A) -> bool {
+        (point - Vec3A::from(self.center))
+            .abs()
+            .cmple(Vec3A::from(self.half_extents))
+            .all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quat;
+
+    #[test]
+    fn aabb_merge() {
+        let a = Aabb::from_min_max(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0));
+        let b = Aabb::from_min_max(Vec3::new(2.0, -3.0, 0.5), Vec3::new(3.0, 0.5, 0.75));
+        let merged = a.merge(&b);
+        assert_eq!(merged.min(), Vec3::new(-1.0, -3.0, 0.0));
+        assert_eq!(merged.max(), Vec3::new(3.0, 1.0, 1.0));
+        assert_eq!(b.merge(&a), merged);
+
+        // merging with a box inside changes nothing
+        let inner = Aabb::new(Vec3::splat(0.5), Vec3::splat(0.25));
+        assert_eq!(a.merge(&inner), a);
+    }
+
+    #[test]
+    fn aabb_contains_point() {
+        let aabb = Aabb::new(Vec3::new(1.0, 2.0, 3.0), Vec3::new(1.0, 0.5, 2.0));
+        assert!(aabb.contains_point(aabb.center.into()));
+        assert!(aabb.contains_point(aabb.min().into()));
+        assert!(aabb.contains_point(aabb.max().into()));
+        assert!(aabb.contains_point(Vec3A::new(1.5, 2.25, 4.0)));
+        assert!(!aabb.contains_point(Vec3A::new(2.5, 2.0, 3.0)));
+        assert!(!aabb.contains_point(Vec3A::new(1.0, 1.0, 3.0)));
+        assert!(!aabb.contains_point(Vec3A::new(1.0, 2.0, 5.5)));
+    }
+
+    #[test]
+    fn aabb_transformed_by_rotation() {
+        let unit = Aabb::new(Vec3::ZERO, Vec3::splat(0.5));
+        let rotation = Mat4::from_quat(Quat::from_rotation_y(std::f32::consts::FRAC_PI_4));
+        let rotated = unit.transformed_by(&rotation);
+        let grown = 0.5 * std::f32::consts::SQRT_2;
+        assert!(rotated.center.abs_diff_eq(Vec3::ZERO, 1e-6));
+        assert!(rotated
+            .half_extents
+            .abs_diff_eq(Vec3::new(grown, 0.5, grown), 1e-6));
+
+        // translation only moves the center
+        let matrix = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)) * rotation;
+        let moved = unit.transformed_by(&matrix);
+        assert!(moved.center.abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), 1e-6));
+        assert!(moved.half_extents.abs_diff_eq(rotated.half_extents, 1e-6));
+    }
+
+    /// Transforms the 8 corners of `aabb` one by one and returns the box around them
+    fn brute_force_transform(aabb: &Aabb, matrix: &Mat4) -> Aabb {
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(f32::NEG_INFINITY);
+        for i in 0..8 {
+            let corner = Vec3::select(
+                crate::BVec3::new(i & 1 != 0, i & 2 != 0, i & 4 != 0),
+                aabb.max(),
+                aabb.min(),
+            );
+            let corner = matrix.transform_point3(corner);
+            min = min.min(corner);
+            max = max.max(corner);
+        }
+        Aabb::from_min_max(min, max)
+    }
+
+    #[test]
+    fn aabb_transformed_by_matches_corners() {
+        let aabb = Aabb::new(Vec3::new(1.0, -2.0, 0.5), Vec3::new(0.5, 2.0, 1.5));
+        let matrices = [
+            Mat4::IDENTITY,
+            Mat4::from_scale(Vec3::new(3.0, 0.5, 2.0)),
+            Mat4::from_scale(Vec3::new(-1.0, 2.0, -0.25)),
+            Mat4::from_scale_rotation_translation(
+                Vec3::new(3.0, 0.5, 2.0),
+                Quat::from_axis_angle(Vec3::new(1.0, 2.0, -1.0).normalize(), 0.7),
+                Vec3::new(-4.0, 1.0, 8.0),
+            ),
+            // non-uniform scale applied after a rotation, which shears the box
+            Mat4::from_scale(Vec3::new(0.2, 4.0, 1.0))
+                * Mat4::from_rotation_z(1.1)
+                * Mat4::from_rotation_x(-0.4),
+        ];
+        for matrix in matrices.iter() {
+            let transformed = aabb.transformed_by(matrix);
+            let expected = brute_force_transform(&aabb, matrix);
+            assert!(
+                transformed.center.abs_diff_eq(expected.center, 1e-5),
+                "{:?} != {:?}",
+                transformed,
+                expected
+            );
+            assert!(
+                transformed
+                    .half_extents
+                    .abs_diff_eq(expected.half_extents, 1e-5),
+                "{:?} != {:?}",
+                transformed,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn aabb_transformed_by_non_uniform_scale() {
+        let unit = Aabb::new(Vec3::ZERO, Vec3::splat(0.5));
+        let scaled = unit.transformed_by(&Mat4::from_scale(Vec3::new(4.0, 1.0, 0.5)));
+        assert_eq!(scaled.half_extents, Vec3::new(2.0, 0.5, 0.25));
+
+        // rotating a stretched box by 90 degrees swaps its extents
+        let matrix = Mat4::from_rotation_z(std::f32::consts::FRAC_PI_2)
+            * Mat4::from_scale(Vec3::new(4.0, 1.0, 0.5));
+        let rotated = unit.transformed_by(&matrix);
+        assert!(rotated
+            .half_extents
+            .abs_diff_eq(Vec3::new(0.5, 2.0, 0.25), 1e-6));
+
+        // mirroring doesn't give negative extents
+        let mirrored = unit.transformed_by(&Mat4::from_scale(Vec3::new(-2.0, 1.0, 1.0)));
+        assert_eq!(mirrored.half_extents, Vec3::new(1.0, 0.5, 0.5));
+    }
+}
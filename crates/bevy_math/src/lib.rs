@@ -1,14 +1,28 @@
+mod aabb;
 mod face_toward;
 mod geometry;
+mod plane;
+mod ray;
+mod rotation;
+mod smoothing;
+mod vector;
 
+pub use aabb::*;
 pub use face_toward::*;
 pub use geometry::*;
 pub use glam::*;
+pub use plane::*;
+pub use ray::*;
+pub use rotation::*;
+pub use smoothing::*;
+pub use vector::*;
+
+pub mod roots;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        BVec2, BVec3, BVec4, FaceToward, IVec2, IVec3, IVec4, Mat3, Mat4, Quat, Rect, Size, UVec2,
-        UVec3, UVec4, Vec2, Vec3, Vec4,
+        BVec2, BVec3, BVec4, FaceToward, IVec2, IVec3, IVec4, Mat3, Mat4, Plane, Quat, Ray, Ray2d,
+        Rect, RotationArc, Size, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4,
     };
 }
@@ -0,0 +1,154 @@
+use crate::Vec3;
+
+/// An infinite plane, made of the points `p` for which `normal.dot(p) + d == 0`
+///
+/// The side `normal` points towards is the positive side of the plane. Most methods expect a
+/// unit length `normal`, so that distances are world space distances: see [`Plane::normalize`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    /// The direction the plane faces
+    pub normal: Vec3,
+    /// The signed distance from the plane to the origin, in multiples of `normal`
+    pub d: f32,
+}
+
+impl Plane {
+    pub fn new(normal: Vec3, d: f32) -> Self {
+        Plane { normal, d }
+    }
+
+    /// Creates the plane going through `point` and facing `normal`
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        Plane {
+            normal,
+            d: -normal.dot(point),
+        }
+    }
+
+    /// Creates the plane going through the three given points, with a unit length normal.
+    ///
+    /// The normal follows the right hand rule: it faces the side from which `a`, `b` and `c`
+    /// appear counter-clockwise, like a front facing triangle.
+    ///
+    /// Returns `None` if the points are collinear, or too close to it to define a plane.
+    pub fn from_points(a: Vec3, b: Vec3, c: Vec3) -> Option<Self> {
+        let ab = b - a;
+        let ac = c - a;
+        let normal = ab.cross(ac);
+        // compare against the length of the edges, so that the result doesn't depend on scale
+        let length_squared = normal.length_squared();
+        if length_squared <= f32::EPSILON * ab.length_squared() * ac.length_squared()
+            || !length_squared.is_finite()
+        {
+            return None;
+        }
+        Some(Plane::from_point_normal(a, normal / length_squared.sqrt()))
+    }
+
+    /// Returns the same plane with a unit length `normal`.
+    ///
+    /// A plane with a zero `normal` has no orientation, and normalizing it gives non-finite
+    /// values.
+    pub fn normalize(self) -> Self {
+        let inv_length = self.normal.length_recip();
+        Plane {
+            normal: self.normal * inv_length,
+            d: self.d * inv_length,
+        }
+    }
+
+    /// Returns the signed distance from the plane to `point`: positive on the side `normal`
+    /// faces, negative behind it and zero on the plane.
+    ///
+    /// The distance is in multiples of the length of `normal`.
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) + self.d
+    }
+
+    /// Returns the point on the plane closest to `point`.
+    ///
+    /// Unlike [`Plane::signed_distance`], this doesn't need a unit length `normal`.
+    pub fn project_point(&self, point: Vec3) -> Vec3 {
+        point - self.normal * (self.signed_distance(point) / self.normal.length_squared())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plane_signed_distance() {
+        let plane = Plane::from_point_normal(Vec3::new(0.0, 2.0, 0.0), Vec3::Y);
+        assert_eq!(plane.d, -2.0);
+        assert_eq!(plane.signed_distance(Vec3::new(5.0, 7.0, -3.0)), 5.0);
+        assert_eq!(plane.signed_distance(Vec3::new(5.0, -1.0, -3.0)), -3.0);
+        assert_eq!(plane.signed_distance(Vec3::new(5.0, 2.0, -3.0)), 0.0);
+
+        // flipping the plane flips the sign
+        let flipped = Plane::new(-plane.normal, -plane.d);
+        assert_eq!(flipped.signed_distance(Vec3::new(5.0, 7.0, -3.0)), -5.0);
+    }
+
+    #[test]
+    fn plane_project_point() {
+        let plane = Plane::from_point_normal(Vec3::ONE, Vec3::new(1.0, 1.0, 0.0).normalize());
+        let projected = plane.project_point(Vec3::new(3.0, 1.0, 7.0));
+        assert!(plane.signed_distance(projected).abs() < 1e-6);
+        assert!(projected.abs_diff_eq(Vec3::new(2.0, 0.0, 7.0), 1e-6));
+
+        // a point on the plane stays where it is
+        assert!(plane.project_point(projected).abs_diff_eq(projected, 1e-6));
+
+        // the normal doesn't need to be unit length
+        let scaled = Plane::new(plane.normal * 3.0, plane.d * 3.0);
+        assert!(scaled
+            .project_point(Vec3::new(3.0, 1.0, 7.0))
+            .abs_diff_eq(projected, 1e-6));
+    }
+
+    #[test]
+    fn plane_from_points_winding() {
+        let (a, b, c) = (
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, -1.0),
+        );
+        let plane = Plane::from_points(a, b, c).unwrap();
+        assert!(plane.normal.abs_diff_eq(Vec3::Y, 1e-6));
+        assert!((plane.d + 1.0).abs() < 1e-6);
+        for point in [a, b, c].iter() {
+            assert!(plane.signed_distance(*point).abs() < 1e-6);
+        }
+
+        // the same winding gives the same plane whichever point comes first, the opposite
+        // winding flips it
+        assert_eq!(Plane::from_points(b, c, a).unwrap().normal, plane.normal);
+        assert_eq!(Plane::from_points(a, c, b).unwrap().normal, -plane.normal);
+    }
+
+    #[test]
+    fn plane_from_collinear_points() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(Plane::from_points(a, a * 2.0, a * -5.0), None);
+        assert_eq!(Plane::from_points(a, a, Vec3::ZERO), None);
+        assert_eq!(Plane::from_points(a, a, a), None);
+        assert_eq!(
+            Plane::from_points(Vec3::ZERO, Vec3::X, Vec3::new(2.0, 1e-6, 0.0)),
+            None
+        );
+
+        // tiny, but well shaped triangles still define a plane
+        let plane = Plane::from_points(Vec3::ZERO, Vec3::X * 1e-3, Vec3::Y * 1e-3).unwrap();
+        assert!(plane.normal.abs_diff_eq(Vec3::Z, 1e-6));
+    }
+
+    #[test]
+    fn plane_normalize() {
+        let plane = Plane::new(Vec3::new(0.0, 3.0, 4.0), 10.0).normalize();
+        assert!(plane.normal.is_normalized());
+        assert!(plane.normal.abs_diff_eq(Vec3::new(0.0, 0.6, 0.8), 1e-6));
+        assert!((plane.d - 2.0).abs() < 1e-6);
+        assert!((plane.signed_distance(Vec3::new(0.0, 0.0, 3.0)) - 4.4).abs() < 1e-6);
+    }
+}
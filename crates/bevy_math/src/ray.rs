@@ -0,0 +1,531 @@
+use crate::{Aabb, Mat4, Vec2, Vec3};
+
+/// A ray is an infinite line starting at `origin`, going in `direction`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    /// The point the ray starts from
+    pub origin: Vec3,
+    /// The direction the ray is pointing in
+    pub direction: Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Ray { origin, direction }
+    }
+
+    /// Creates a ray with a unit length `direction`, so that distances along it are world space
+    /// distances.
+    ///
+    /// # Panics
+    /// Panics in debug builds if `direction` is zero or not finite, as it can't be normalized.
+    pub fn new_normalized(origin: Vec3, direction: Vec3) -> Self {
+        let direction = direction.normalize();
+        debug_assert!(
+            direction.is_finite(),
+            "ray direction must be non-zero and finite"
+        );
+        Ray { origin, direction }
+    }
+
+    /// Creates a ray starting at `start` and pointing towards `end`, with a unit length
+    /// `direction`.
+    ///
+    /// If `start == end` there is no direction to point in, and the ray's `direction` is zero.
+    /// Such a ray only "hits" shapes that contain its origin.
+    pub fn from_two_points(start: Vec3, end: Vec3) -> Self {
+        Ray {
+            origin: start,
+            direction: (end - start).normalize_or_zero(),
+        }
+    }
+
+    /// Creates the world space ray going through the point `ndc` of the screen, in normalized
+    /// device coordinates, for a camera with the inverse view projection matrix
+    /// `view_proj_inverse`.
+    ///
+    /// The ray starts on the near plane and has a unit length `direction`, pointing away from the
+    /// camera. Starting on the near plane rather than at the camera position makes this work for
+    /// orthographic cameras as well, where the rays for each screen point are parallel.
+    pub fn from_camera(view_proj_inverse: Mat4, ndc: Vec2) -> Self {
+        // depth goes from 0 on the near plane to 1 on the far plane
+        let near = view_proj_inverse.project_point3(ndc.extend(0.0));
+        let far = view_proj_inverse.project_point3(ndc.extend(1.0));
+        Ray::from_two_points(near, far)
+    }
+
+    /// Creates the world space ray going through the pixel at `position` of a viewport of
+    /// `viewport_size` pixels, for a camera with the inverse view projection matrix
+    /// `view_proj_inverse`.
+    ///
+    /// `position` is measured from the bottom left corner of the viewport, like the cursor
+    /// position of a window. See [`Ray::from_camera`].
+    pub fn from_viewport(view_proj_inverse: Mat4, position: Vec2, viewport_size: Vec2) -> Self {
+        let ndc = position / viewport_size * 2.0 - Vec2::ONE;
+        Ray::from_camera(view_proj_inverse, ndc)
+    }
+
+    /// Returns the point at `distance` along the ray, in multiples of `direction`
+    pub fn get_point(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Returns the distances along `self` and along `other` of the two points where the rays
+    /// come closest to each other, in multiples of their `direction`s.
+    ///
+    /// Both rays are treated as infinite lines, so either distance may be negative. Parallel rays
+    /// are equally close everywhere: `self.origin` is then projected onto `other`, giving a
+    /// distance of zero along `self`.
+    pub fn closest_point_to_ray(&self, other: &Ray) -> (f32, f32) {
+        let between_origins = self.origin - other.origin;
+        let a = self.direction.length_squared();
+        let b = self.direction.dot(other.direction);
+        let c = other.direction.length_squared();
+        let d = self.direction.dot(between_origins);
+        let e = other.direction.dot(between_origins);
+
+        let denominator = a * c - b * b;
+        if denominator > f32::EPSILON * a * c {
+            ((b * e - c * d) / denominator, (a * e - b * d) / denominator)
+        } else if c > 0.0 {
+            (0.0, e / c)
+        } else if a > 0.0 {
+            // `other` has no direction: project its origin onto `self` instead
+            (-d / a, 0.0)
+        } else {
+            (0.0, 0.0)
+        }
+    }
+
+    /// Returns the ray bouncing off a surface with the given `normal` at `hit_point`.
+    ///
+    /// `normal` doesn't need to be normalized, and may face either side of the surface.
+    pub fn reflect(&self, hit_point: Vec3, normal: Vec3) -> Ray {
+        let normal = normal.normalize();
+        Ray {
+            origin: hit_point,
+            direction: self.direction - 2.0 * self.direction.dot(normal) * normal,
+        }
+    }
+
+    /// Returns the ray refracted through a surface with the given `normal` at `hit_point`,
+    /// following Snell's law. The returned ray has a unit length `direction`.
+    ///
+    /// `eta` is the ratio of the refractive index of the medium the ray travels in, to the one
+    /// of the medium it is entering. `normal` doesn't need to be normalized, and may face either
+    /// side of the surface.
+    ///
+    /// Returns `None` on total internal reflection.
+    pub fn refract(&self, hit_point: Vec3, normal: Vec3, eta: f32) -> Option<Ray> {
+        let direction = self.direction.normalize();
+        let mut normal = normal.normalize();
+        let mut cos_incident = -direction.dot(normal);
+        if cos_incident < 0.0 {
+            // make the normal face the incoming ray
+            normal = -normal;
+            cos_incident = -cos_incident;
+        }
+
+        let k = 1.0 - eta * eta * (1.0 - cos_incident * cos_incident);
+        if k < 0.0 {
+            return None;
+        }
+
+        Some(Ray {
+            origin: hit_point,
+            direction: eta * direction + (eta * cos_incident - k.sqrt()) * normal,
+        })
+    }
+
+    /// Intersects the ray with an [`Aabb`] using the slab method.
+    ///
+    /// Returns the distances along the ray at which it enters and exits the box, or `None` if
+    /// the ray misses it entirely. Distances are measured in multiples of `direction`. When the
+    /// ray starts inside the box, the entry distance is negative.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<(f32, f32)> {
+        intersect_slabs(
+            &self.origin.to_array(),
+            &self.direction.to_array(),
+            &aabb.min().to_array(),
+            &aabb.max().to_array(),
+        )
+    }
+}
+
+/// A two dimensional ray, starting at `origin` and going in `direction`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray2d {
+    /// The point the ray starts from
+    pub origin: Vec2,
+    /// The direction the ray is pointing in
+    pub direction: Vec2,
+}
+
+impl Ray2d {
+    pub fn new(origin: Vec2, direction: Vec2) -> Self {
+        Ray2d { origin, direction }
+    }
+
+    /// Returns the same ray with a unit length `direction`, so that intersection results are
+    /// world space distances
+    pub fn normalize(self) -> Self {
+        Ray2d {
+            origin: self.origin,
+            direction: self.direction.normalize(),
+        }
+    }
+
+    /// Intersects the ray with the line segment going from `start` to `end`.
+    ///
+    /// Returns the distance along the ray to the intersection, in multiples of `direction`.
+    /// A segment parallel to the ray is never considered hit.
+    pub fn intersect_line_segment(&self, start: Vec2, end: Vec2) -> Option<f32> {
+        let segment = end - start;
+        let denominator = self.direction.perp_dot(segment);
+        if denominator == 0.0 {
+            return None;
+        }
+
+        let to_start = start - self.origin;
+        let t = to_start.perp_dot(segment) / denominator;
+        let s = to_start.perp_dot(self.direction) / denominator;
+        if t >= 0.0 && (0.0..=1.0).contains(&s) {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// Intersects the ray with the axis aligned rectangle going from `min` to `max`, which are
+    /// expected to be its lower left and upper right corners.
+    ///
+    /// Returns the distances along the ray at which it enters and exits the rectangle, or `None`
+    /// if the ray misses it entirely. When the ray starts inside the rectangle, the entry distance
+    /// is negative.
+    pub fn intersect_rect(&self, min: Vec2, max: Vec2) -> Option<(f32, f32)> {
+        intersect_slabs(
+            &self.origin.to_array(),
+            &self.direction.to_array(),
+            &min.to_array(),
+            &max.to_array(),
+        )
+    }
+
+    /// Intersects the ray with a circle.
+    ///
+    /// Returns the distances along the ray at which it enters and exits the circle, or `None`
+    /// if the ray misses it entirely. When the ray starts inside the circle, the entry distance
+    /// is negative.
+    pub fn intersect_circle(&self, center: Vec2, radius: f32) -> Option<(f32, f32)> {
+        let a = self.direction.length_squared();
+        if a == 0.0 {
+            return None;
+        }
+
+        let to_origin = self.origin - center;
+        let half_b = self.direction.dot(to_origin);
+        let c = to_origin.length_squared() - radius * radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let sqrt_discriminant = discriminant.sqrt();
+        let t_near = (-half_b - sqrt_discriminant) / a;
+        let t_far = (-half_b + sqrt_discriminant) / a;
+        if t_far < 0.0 {
+            None
+        } else {
+            Some((t_near, t_far))
+        }
+    }
+}
+
+/// Slab test shared by the 2D and 3D rays. Each slice holds one value per axis.
+fn intersect_slabs(
+    origin: &[f32],
+    direction: &[f32],
+    min: &[f32],
+    max: &[f32],
+) -> Option<(f32, f32)> {
+    let mut t_near = f32::NEG_INFINITY;
+    let mut t_far = f32::INFINITY;
+    for axis in 0..origin.len() {
+        if direction[axis] == 0.0 {
+            // the ray is parallel to this slab, so it either always or never overlaps it
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let inv_direction = direction[axis].recip();
+        let t0 = (min[axis] - origin[axis]) * inv_direction;
+        let t1 = (max[axis] - origin[axis]) * inv_direction;
+        let (t0, t1) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+        t_near = t_near.max(t0);
+        t_far = t_far.min(t1);
+    }
+
+    if t_near > t_far || t_far < 0.0 {
+        None
+    } else {
+        Some((t_near, t_far))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_box() -> Aabb {
+        Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0))
+    }
+
+    #[test]
+    fn ray_from_two_points() {
+        let ray = Ray::from_two_points(Vec3::new(1.0, 2.0, 3.0), Vec3::new(1.0, 2.0, -7.0));
+        assert_eq!(ray.origin, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(ray.direction, -Vec3::Z);
+
+        let ray = Ray::from_two_points(Vec3::ONE, Vec3::ONE);
+        assert_eq!(ray.direction, Vec3::ZERO);
+    }
+
+    #[test]
+    fn ray_new_normalized() {
+        let ray = Ray::new_normalized(Vec3::ZERO, Vec3::new(0.0, 3.0, 4.0));
+        assert!(ray.direction.is_normalized());
+        assert_eq!(ray.direction, Vec3::new(0.0, 0.6, 0.8));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn ray_new_normalized_zero_direction() {
+        Ray::new_normalized(Vec3::ZERO, Vec3::ZERO);
+    }
+
+    #[test]
+    fn ray_from_perspective_camera() {
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 2.0, 0.5, 100.0);
+        let view = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let view_proj_inverse = view * projection.inverse();
+
+        let ray = Ray::from_camera(view_proj_inverse, Vec2::ZERO);
+        assert!(ray.origin.abs_diff_eq(Vec3::new(1.0, 2.0, 2.5), 1e-4));
+        assert!(ray.direction.abs_diff_eq(-Vec3::Z, 1e-4));
+
+        // a 90 degree vertical field of view puts the top of the screen at 45 degrees
+        let ray = Ray::from_camera(view_proj_inverse, Vec2::Y);
+        assert!(ray.direction.is_normalized());
+        assert!(ray
+            .direction
+            .abs_diff_eq(Vec3::new(0.0, 1.0, -1.0).normalize(), 1e-4));
+        // every ray goes through the camera position
+        let behind = ray.origin - ray.direction * (ray.origin - view.w_axis.truncate()).length();
+        assert!(behind.abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), 1e-4));
+    }
+
+    #[test]
+    fn ray_from_orthographic_camera() {
+        let projection = Mat4::orthographic_rh(-4.0, 4.0, -2.0, 2.0, 0.0, 100.0);
+        let view = Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let view_proj_inverse = view * projection.inverse();
+
+        // parallel rays, starting on the near plane at different points
+        let center = Ray::from_camera(view_proj_inverse, Vec2::ZERO);
+        let corner = Ray::from_camera(view_proj_inverse, Vec2::new(1.0, -1.0));
+        assert!(center.origin.abs_diff_eq(Vec3::new(0.0, 0.0, 10.0), 1e-4));
+        assert!(corner.origin.abs_diff_eq(Vec3::new(4.0, -2.0, 10.0), 1e-4));
+        assert!(center.direction.abs_diff_eq(-Vec3::Z, 1e-6));
+        assert!(corner.direction.abs_diff_eq(-Vec3::Z, 1e-6));
+    }
+
+    #[test]
+    fn ray_from_viewport() {
+        let projection = Mat4::orthographic_rh(-4.0, 4.0, -2.0, 2.0, 0.0, 100.0);
+        let size = Vec2::new(800.0, 400.0);
+        let ray = Ray::from_viewport(projection.inverse(), size / 2.0, size);
+        assert!(ray.origin.abs_diff_eq(Vec3::ZERO, 1e-4));
+
+        // pixels are measured from the bottom left corner
+        let ray = Ray::from_viewport(projection.inverse(), Vec2::new(0.0, 100.0), size);
+        assert!(ray.origin.abs_diff_eq(Vec3::new(-4.0, -1.0, 0.0), 1e-4));
+    }
+
+    #[test]
+    fn ray_get_point() {
+        let ray = Ray::new(Vec3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 2.0, 0.0));
+        assert_eq!(ray.get_point(0.0), ray.origin);
+        assert_eq!(ray.get_point(1.5), Vec3::new(1.0, 5.0, 3.0));
+        assert_eq!(ray.get_point(-1.0), Vec3::new(1.0, 0.0, 3.0));
+    }
+
+    #[test]
+    fn ray_closest_point_to_skew_ray() {
+        let x_axis = Ray::new(Vec3::ZERO, Vec3::X);
+        let mouse = Ray::new(Vec3::new(3.0, 5.0, 4.0), Vec3::new(0.0, 0.0, -2.0));
+        let (t, s) = x_axis.closest_point_to_ray(&mouse);
+        assert!((t - 3.0).abs() < 1e-6);
+        assert!((s - 2.0).abs() < 1e-6);
+        assert!(mouse
+            .get_point(s)
+            .abs_diff_eq(Vec3::new(3.0, 5.0, 0.0), 1e-6));
+
+        // the segment joining the closest points is orthogonal to both rays
+        let a = Ray::new(Vec3::new(1.0, -2.0, 0.5), Vec3::new(1.0, 1.0, 0.0));
+        let b = Ray::new(Vec3::new(-3.0, 0.0, 2.0), Vec3::new(0.0, 1.0, 3.0));
+        let (t, s) = a.closest_point_to_ray(&b);
+        let between = a.get_point(t) - b.get_point(s);
+        assert!(between.dot(a.direction).abs() < 1e-5);
+        assert!(between.dot(b.direction).abs() < 1e-5);
+        let (s2, t2) = b.closest_point_to_ray(&a);
+        assert!((s - s2).abs() < 1e-5 && (t - t2).abs() < 1e-5);
+
+        // intersecting rays meet, even behind their origins
+        let a = Ray::new(Vec3::new(2.0, 0.0, 0.0), Vec3::X);
+        let b = Ray::new(Vec3::new(0.0, 3.0, 0.0), Vec3::Y);
+        assert_eq!(a.closest_point_to_ray(&b), (-2.0, -3.0));
+    }
+
+    #[test]
+    fn ray_closest_point_to_parallel_ray() {
+        let a = Ray::new(Vec3::new(1.0, 0.0, 0.0), Vec3::X);
+        let b = Ray::new(Vec3::new(5.0, 2.0, 0.0), Vec3::new(-2.0, 0.0, 0.0));
+        let (t, s) = a.closest_point_to_ray(&b);
+        assert!(t.is_finite() && s.is_finite());
+        assert_eq!((t, s), (0.0, 2.0));
+        assert_eq!(b.get_point(s), Vec3::new(1.0, 2.0, 0.0));
+
+        // rays without a direction are just points
+        let point = Ray::new(Vec3::new(4.0, 1.0, 0.0), Vec3::ZERO);
+        assert_eq!(a.closest_point_to_ray(&point), (3.0, 0.0));
+        assert_eq!(point.closest_point_to_ray(&a), (0.0, 3.0));
+        assert_eq!(point.closest_point_to_ray(&point), (0.0, 0.0));
+    }
+
+    #[test]
+    fn ray_reflect() {
+        let ray = Ray::new(
+            Vec3::new(-1.0, 1.0, 0.0),
+            Vec3::new(1.0, -1.0, 0.0).normalize(),
+        );
+        let reflected = ray.reflect(Vec3::ZERO, Vec3::Y);
+        assert_eq!(reflected.origin, Vec3::ZERO);
+        assert!(reflected
+            .direction
+            .abs_diff_eq(Vec3::new(1.0, 1.0, 0.0).normalize(), 1e-6));
+
+        // the side the normal faces doesn't matter
+        assert_eq!(ray.reflect(Vec3::ZERO, -2.0 * Vec3::Y), reflected);
+    }
+
+    #[test]
+    fn ray_refract() {
+        let direction = Vec3::new(1.0, -1.0, 0.0).normalize();
+        let ray = Ray::new(Vec3::new(-1.0, 1.0, 0.0), direction);
+
+        // matching media don't bend the ray
+        let refracted = ray.refract(Vec3::ZERO, Vec3::Y, 1.0).unwrap();
+        assert!(refracted.direction.abs_diff_eq(direction, 1e-6));
+
+        // entering a denser medium bends the ray towards the normal, following Snell's law
+        let refracted = ray.refract(Vec3::ZERO, Vec3::Y, 1.0 / 1.5).unwrap();
+        assert!(refracted.direction.is_normalized());
+        let sin_refracted = refracted.direction.x;
+        assert!((sin_refracted - direction.x / 1.5).abs() < 1e-6);
+        assert!(refracted.direction.y < 0.0);
+
+        // leaving a denser medium at a grazing angle is total internal reflection
+        assert_eq!(ray.refract(Vec3::ZERO, Vec3::Y, 1.5), None);
+    }
+
+    #[test]
+    fn ray_hits_aabb() {
+        let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((4.0, 6.0)));
+
+        let ray = Ray::new(Vec3::new(-5.0, -5.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((4.0, 6.0)));
+    }
+
+    #[test]
+    fn ray_misses_aabb() {
+        // parallel to the x slab, but outside of it
+        let ray = Ray::new(Vec3::new(2.0, -5.0, 0.0), Vec3::Y);
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+
+        // pointing away from the box
+        let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), -Vec3::X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+
+        // passes beside the box diagonally
+        let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+    }
+
+    #[test]
+    fn ray_starting_inside_aabb() {
+        let ray = Ray::new(Vec3::ZERO, Vec3::Z);
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((-1.0, 1.0)));
+    }
+
+    #[test]
+    fn ray_along_aabb_face() {
+        let ray = Ray::new(Vec3::new(-5.0, 1.0, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((4.0, 6.0)));
+    }
+
+    #[test]
+    fn ray2d_hits_rect() {
+        let (min, max) = (-Vec2::ONE, Vec2::ONE);
+        let ray = Ray2d::new(Vec2::new(-5.0, 0.0), Vec2::X);
+        assert_eq!(ray.intersect_rect(min, max), Some((4.0, 6.0)));
+
+        let ray = Ray2d::new(Vec2::ZERO, Vec2::Y);
+        assert_eq!(ray.intersect_rect(min, max), Some((-1.0, 1.0)));
+    }
+
+    #[test]
+    fn ray2d_misses_rect() {
+        let (min, max) = (-Vec2::ONE, Vec2::ONE);
+        let ray = Ray2d::new(Vec2::new(-5.0, 2.0), Vec2::X);
+        assert_eq!(ray.intersect_rect(min, max), None);
+
+        let ray = Ray2d::new(Vec2::new(-5.0, 0.0), -Vec2::X);
+        assert_eq!(ray.intersect_rect(min, max), None);
+    }
+
+    #[test]
+    fn ray2d_line_segment() {
+        let ray = Ray2d::new(Vec2::ZERO, Vec2::new(2.0, 0.0)).normalize();
+        assert_eq!(
+            ray.intersect_line_segment(Vec2::new(3.0, -1.0), Vec2::new(3.0, 1.0)),
+            Some(3.0)
+        );
+        // behind the ray
+        assert_eq!(
+            ray.intersect_line_segment(Vec2::new(-3.0, -1.0), Vec2::new(-3.0, 1.0)),
+            None
+        );
+        // the line is crossed, but outside of the segment
+        assert_eq!(
+            ray.intersect_line_segment(Vec2::new(3.0, 1.0), Vec2::new(3.0, 2.0)),
+            None
+        );
+        // parallel
+        assert_eq!(
+            ray.intersect_line_segment(Vec2::new(0.0, 1.0), Vec2::new(3.0, 1.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn ray2d_circle() {
+        let ray = Ray2d::new(Vec2::new(-5.0, 0.0), Vec2::X);
+        assert_eq!(ray.intersect_circle(Vec2::ZERO, 2.0), Some((3.0, 7.0)));
+        assert_eq!(ray.intersect_circle(Vec2::new(0.0, 3.0), 2.0), None);
+        assert_eq!(ray.intersect_circle(Vec2::new(-8.0, 0.0), 2.0), None);
+    }
+}
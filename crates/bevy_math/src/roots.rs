@@ -0,0 +1,219 @@
+//! Real roots of low degree polynomials.
+//!
+//! The solvers work in `f64` internally and return the roots sorted in ascending order. A root of
+//! multiplicity two or three is only returned once, and so are roots too close together to be
+//! told apart at `f32` precision. Roots that aren't finite, such as the ones NaN or infinite
+//! coefficients give, are left out.
+
+use arrayvec::ArrayVec;
+
+/// Returns the real roots of `a * x^2 + b * x + c`.
+///
+/// When `a` is zero this falls back to the linear equation `b * x + c`, which has no roots if `b`
+/// is zero as well. The roots are computed with the "citardauq" form, which avoids the
+/// cancellation of the textbook formula when `b * b` is much larger than `4 * a * c`.
+pub fn solve_quadratic(a: f32, b: f32, c: f32) -> ArrayVec<[f32; 2]> {
+    let mut roots = ArrayVec::new();
+    for root in quadratic_roots(a as f64, b as f64, c as f64) {
+        push_root(&mut roots, root);
+    }
+    roots
+}
+
+/// Returns the real roots of `a * x^3 + b * x^2 + c * x + d`.
+///
+/// When `a` is zero this falls back to [`solve_quadratic`].
+pub fn solve_cubic(a: f32, b: f32, c: f32, d: f32) -> ArrayVec<[f32; 3]> {
+    let mut roots = ArrayVec::new();
+    if a == 0.0 {
+        for root in quadratic_roots(b as f64, c as f64, d as f64) {
+            push_root(&mut roots, root);
+        }
+        return roots;
+    }
+
+    let (a, b, c, d) = (a as f64, b as f64, c as f64, d as f64);
+    let (b, c, d) = (b / a, c / a, d / a);
+
+    // substituting x = t - b / 3 gives the depressed cubic t^3 + p * t + q
+    let shift = -b / 3.0;
+    let p = c - b * b / 3.0;
+    let q = (2.0 * b * b * b - 9.0 * b * c) / 27.0 + d;
+
+    let half_q = q / 2.0;
+    let third_p = p / 3.0;
+    let discriminant = half_q * half_q + third_p * third_p * third_p;
+    let tolerance = f32::EPSILON as f64 * (half_q * half_q + (third_p * third_p * third_p).abs());
+
+    let mut depressed_roots = ArrayVec::<[f64; 3]>::new();
+    if discriminant.abs() <= tolerance {
+        if p.abs() <= f32::EPSILON as f64 * (b * b).max(c.abs()) {
+            // triple root
+            depressed_roots.push(0.0);
+        } else {
+            // a single and a double root
+            depressed_roots.push(3.0 * q / p);
+            depressed_roots.push(-1.5 * q / p);
+        }
+    } else if discriminant > 0.0 {
+        // a single real root, using Cardano's formula with the sign of `q` picked so that the
+        // two cube roots don't cancel out
+        let u = -(half_q.abs() + discriminant.sqrt()).cbrt() * half_q.signum();
+        let v = if u == 0.0 { 0.0 } else { -third_p / u };
+        depressed_roots.push(u + v);
+    } else {
+        // three distinct real roots, using the trigonometric form
+        let radius = 2.0 * (-third_p).sqrt();
+        let angle = (3.0 * q / (p * radius)).clamp(-1.0, 1.0).acos() / 3.0;
+        for k in 0..3 {
+            let offset = k as f64 * 2.0 * std::f64::consts::FRAC_PI_3;
+            depressed_roots.push(radius * (angle - offset).cos());
+        }
+    }
+
+    let mut cubic_roots = depressed_roots
+        .iter()
+        .map(|t| polish_cubic_root(b, c, d, t + shift))
+        .filter(|root| root.is_finite())
+        .collect::<ArrayVec<[f64; 3]>>();
+    // non-finite roots are gone, so the comparison can't fail
+    cubic_roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for root in cubic_roots {
+        push_root(&mut roots, root);
+    }
+    roots
+}
+
+fn quadratic_roots(a: f64, b: f64, c: f64) -> ArrayVec<[f64; 2]> {
+    let mut roots = ArrayVec::new();
+    if a == 0.0 {
+        if b != 0.0 {
+            roots.push(-c / b);
+        }
+        return roots;
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    // the coefficients only carry f32 precision, so a discriminant within its rounding error of
+    // zero is a double root
+    let tolerance = f32::EPSILON as f64 * (b * b + (4.0 * a * c).abs());
+    if discriminant.abs() <= tolerance {
+        roots.push(-b / (2.0 * a));
+    } else if discriminant > 0.0 {
+        let q = -0.5 * (b + discriminant.sqrt().copysign(b));
+        let (x0, x1) = (q / a, c / q);
+        roots.push(x0.min(x1));
+        roots.push(x0.max(x1));
+    }
+    roots
+}
+
+/// Refines a root of the monic cubic `x^3 + b * x^2 + c * x + d` with a few Newton iterations.
+fn polish_cubic_root(b: f64, c: f64, d: f64, mut x: f64) -> f64 {
+    for _ in 0..2 {
+        let value = ((x + b) * x + c) * x + d;
+        let derivative = (3.0 * x + 2.0 * b) * x + c;
+        if derivative == 0.0 {
+            break;
+        }
+        x -= value / derivative;
+    }
+    x
+}
+
+/// Pushes `root` unless it isn't finite as an `f32`, or is the same `f32` as the previous, smaller
+/// root.
+fn push_root<A: arrayvec::Array<Item = f32>>(roots: &mut ArrayVec<A>, root: f64) {
+    let root = root as f32;
+    if root.is_finite() && roots.last() != Some(&root) {
+        roots.push(root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(roots: &[f32], expected: &[f32]) {
+        assert_eq!(roots.len(), expected.len(), "{:?} != {:?}", roots, expected);
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!(
+                (root - expected).abs() <= 1e-5 * expected.abs().max(1.0),
+                "{:?} != {:?}",
+                roots,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn quadratic() {
+        // (x - 2)(x + 3)
+        assert_roots(&solve_quadratic(1.0, 1.0, -6.0), &[-3.0, 2.0]);
+        // 2(x - 0.5)(x - 4)
+        assert_roots(&solve_quadratic(2.0, -9.0, 4.0), &[0.5, 4.0]);
+        assert_roots(&solve_quadratic(1.0, 0.0, 1.0), &[]);
+        assert_roots(&solve_quadratic(1.0, 0.0, 0.0), &[0.0]);
+    }
+
+    #[test]
+    fn quadratic_double_root() {
+        // (x - 3)^2
+        assert_roots(&solve_quadratic(1.0, -6.0, 9.0), &[3.0]);
+        // (x - 0.1)^2, whose coefficients aren't exact in f32
+        assert_roots(&solve_quadratic(1.0, -0.2, 0.01), &[0.1]);
+    }
+
+    #[test]
+    fn quadratic_without_cancellation() {
+        // (x - 1e4)(x - 1e-4): the textbook formula loses the small root entirely in f32
+        assert_roots(&solve_quadratic(1.0, -10000.0001, 1.0), &[1e-4, 1e4]);
+    }
+
+    #[test]
+    fn quadratic_degenerate() {
+        assert_roots(&solve_quadratic(0.0, 2.0, -3.0), &[1.5]);
+        assert_roots(&solve_quadratic(0.0, 0.0, 1.0), &[]);
+        assert_roots(&solve_quadratic(0.0, 0.0, 0.0), &[]);
+    }
+
+    #[test]
+    fn cubic() {
+        // (x - 1)(x - 2)(x - 3)
+        assert_roots(&solve_cubic(1.0, -6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+        // 2(x + 1)(x - 0.5)(x - 10)
+        assert_roots(&solve_cubic(2.0, -19.0, -11.0, 10.0), &[-1.0, 0.5, 10.0]);
+        // (x - 2)(x^2 + 1)
+        assert_roots(&solve_cubic(1.0, -2.0, 1.0, -2.0), &[2.0]);
+        // x^3 - x
+        assert_roots(&solve_cubic(1.0, 0.0, -1.0, 0.0), &[-1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn cubic_multiple_roots() {
+        // (x - 1)^2 (x + 2)
+        assert_roots(&solve_cubic(1.0, 0.0, -3.0, 2.0), &[-2.0, 1.0]);
+        // (x + 1)(x - 3)^2
+        assert_roots(&solve_cubic(1.0, -5.0, 3.0, 9.0), &[-1.0, 3.0]);
+        // (x - 2)^3
+        assert_roots(&solve_cubic(1.0, -6.0, 12.0, -8.0), &[2.0]);
+        assert_roots(&solve_cubic(3.0, 0.0, 0.0, 0.0), &[0.0]);
+    }
+
+    #[test]
+    fn cubic_degenerate() {
+        assert_roots(&solve_cubic(0.0, 1.0, 1.0, -6.0), &[-3.0, 2.0]);
+        assert_roots(&solve_cubic(0.0, 0.0, 2.0, -3.0), &[1.5]);
+        assert_roots(&solve_cubic(0.0, 0.0, 0.0, 1.0), &[]);
+    }
+
+    #[test]
+    fn non_finite_coefficients() {
+        assert_roots(&solve_cubic(f32::NAN, 1.0, 2.0, 3.0), &[]);
+        assert_roots(&solve_cubic(1.0, f32::NAN, 2.0, 3.0), &[]);
+        assert_roots(&solve_cubic(1.0, -6.0, f32::INFINITY, -6.0), &[]);
+        assert_roots(&solve_cubic(0.0, 1.0, f32::NAN, 3.0), &[]);
+        assert_roots(&solve_cubic(0.0, 0.0, f32::NAN, 3.0), &[]);
+        assert_roots(&solve_quadratic(f32::NAN, 1.0, 1.0), &[]);
+    }
+}
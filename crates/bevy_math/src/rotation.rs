@@ -0,0 +1,375 @@
+use crate::{Mat3, Quat, Vec3, Vec4};
+
+/// Generates the shortest rotation that turns one direction into another
+pub trait RotationArc {
+    /// Generates the shortest rotation that turns the direction `from` into the direction `to`.
+    ///
+    /// Neither vector needs to be normalized. When the directions are identical, or either of
+    /// them is zero, the identity rotation is returned. When they are opposite, the result is a
+    /// half turn around an arbitrary axis perpendicular to `from`.
+    fn rotation_arc(from: Vec3, to: Vec3) -> Self;
+}
+
+impl RotationArc for Quat {
+    fn rotation_arc(from: Vec3, to: Vec3) -> Self {
+        let from = from.normalize_or_zero();
+        let to = to.normalize_or_zero();
+        if from == Vec3::ZERO || to == Vec3::ZERO {
+            return Quat::IDENTITY;
+        }
+        Quat::from_rotation_arc(from, to)
+    }
+}
+
+/// Samples a path of orientations going through each of the `keys` in order, using spherical
+/// linear interpolation between consecutive keys.
+///
+/// `t` ranges over the whole path, from `0.0` at the first key to `1.0` at the last one, with
+/// the keys spread evenly in between. It is clamped to that range. Consecutive keys are always
+/// interpolated along the shortest path, even if their signs differ. An empty `keys` slice
+/// gives the identity rotation.
+pub fn slerp_path(keys: &[Quat], t: f32) -> Quat {
+    let (i, local_t) = match path_segment(keys, t) {
+        Some(segment) => segment,
+        None => return keys.first().copied().unwrap_or(Quat::IDENTITY),
+    };
+    let start = keys[i];
+    let end = shortest_to(start, keys[i + 1]);
+    start.slerp(end, local_t).normalize()
+}
+
+/// Samples a smooth path of orientations going through each of the `keys` in order, using
+/// spherical quadrangle interpolation (squad).
+///
+/// Unlike [`slerp_path`], the angular velocity of the path is continuous through the keys.
+/// `t` is interpreted as in [`slerp_path`].
+pub fn smooth_quat_path(keys: &[Quat], t: f32) -> Quat {
+    let (i, local_t) = match path_segment(keys, t) {
+        Some(segment) => segment,
+        None => return keys.first().copied().unwrap_or(Quat::IDENTITY),
+    };
+
+    // make the four keys around the segment hemisphere-consistent, so no step takes the long way
+    let q1 = keys[i];
+    let q0 = shortest_to(q1, keys[i.saturating_sub(1)]);
+    let q2 = shortest_to(q1, keys[i + 1]);
+    let q3 = shortest_to(q2, keys[(i + 2).min(keys.len() - 1)]);
+
+    let s1 = squad_control_point(q0, q1, q2);
+    let s2 = squad_control_point(q1, q2, q3);
+    let outer = q1.slerp(q2, local_t);
+    let inner = s1.slerp(shortest_to(s1, s2), local_t);
+    outer
+        .slerp(shortest_to(outer, inner), 2.0 * local_t * (1.0 - local_t))
+        .normalize()
+}
+
+/// Rotates `from` towards `to` by at most `max_radians`, along the shortest arc.
+///
+/// `to` is returned as is once it is within `max_radians` of `from`, and a `max_radians` of zero
+/// or less leaves `from` unchanged. Both rotations are expected to be normalized. Opposite signs
+/// of the same rotation are handled, and so is a half turn between the two.
+pub fn quat_rotate_towards(from: Quat, to: Quat, max_radians: f32) -> Quat {
+    let to_shortest = shortest_to(from, to);
+    let half_angle = from.dot(to_shortest).min(1.0).acos();
+    let angle = 2.0 * half_angle;
+    if angle <= max_radians.max(0.0) {
+        return to;
+    }
+    if max_radians <= 0.0 {
+        return from;
+    }
+
+    // here 0 < max_radians < angle, so 0 < t < 1 and the half angle is in (0, pi / 2] along the
+    // shortest arc: its sine is positive, however small the angle, and the slerp is well defined
+    let t = max_radians / angle;
+    let sin_half_angle = half_angle.sin();
+    let a = ((1.0 - t) * half_angle).sin() / sin_half_angle;
+    let b = (t * half_angle).sin() / sin_half_angle;
+    Quat::from_vec4(Vec4::from(from) * a + Vec4::from(to_shortest) * b).normalize()
+}
+
+/// Returns the orientation whose forward direction (local `-Z`) points along `forward`, with its
+/// local `Y` axis as close to `up` as possible.
+///
+/// This matches the convention of `Transform::looking_at` and [`FaceToward`](crate::FaceToward).
+/// Neither vector needs to be normalized. When `up` is zero or parallel to `forward`, another up
+/// axis is picked instead: `Y`, or `Z` when `forward` is close to vertical. A zero `forward`
+/// gives the identity rotation.
+pub fn quat_looking_to(forward: Vec3, up: Vec3) -> Quat {
+    let back = -forward.normalize_or_zero();
+    if back == Vec3::ZERO {
+        return Quat::IDENTITY;
+    }
+    let mut right = up.cross(back);
+    if right.length_squared() < 1e-12 {
+        let fallback_up = if back.y.abs() < 0.9 { Vec3::Y } else { Vec3::Z };
+        right = fallback_up.cross(back);
+    }
+    let right = right.normalize();
+    let up = back.cross(right);
+    Quat::from_mat3(&Mat3::from_cols(right, up, back)).normalize()
+}
+
+/// Returns the orientation of something at `from` looking at `to`, with its local `Y` axis as
+/// close to `up` as possible.
+///
+/// See [`quat_looking_to`]. When `from` and `to` are the same, the identity rotation is returned.
+pub fn quat_looking_at(from: Vec3, to: Vec3, up: Vec3) -> Quat {
+    quat_looking_to(to - from, up)
+}
+
+/// Finds the segment of a path of `keys` that `t` falls in, and the position within it. Returns
+/// `None` when there are fewer than two keys.
+fn path_segment(keys: &[Quat], t: f32) -> Option<(usize, f32)> {
+    if keys.len() < 2 {
+        return None;
+    }
+    let segments = keys.len() - 1;
+    let scaled = t.clamp(0.0, 1.0) * segments as f32;
+    let i = (scaled as usize).min(segments - 1);
+    Some((i, scaled - i as f32))
+}
+
+/// Returns whichever of `q` and `-q` is closest to `from`
+fn shortest_to(from: Quat, q: Quat) -> Quat {
+    if from.dot(q) < 0.0 {
+        -q
+    } else {
+        q
+    }
+}
+
+/// The inner control point of a squad segment at `current`
+fn squad_control_point(previous: Quat, current: Quat, next: Quat) -> Quat {
+    let inverse = current.conjugate();
+    let tangent = -(quat_log(inverse * next) + quat_log(inverse * previous)) / 4.0;
+    (current * quat_exp(tangent)).normalize()
+}
+
+/// The logarithm of a unit quaternion, as the vector part of a pure quaternion
+fn quat_log(q: Quat) -> Vec3 {
+    let v = Vec3::new(q.x, q.y, q.z);
+    let sin_half_angle = v.length();
+    if sin_half_angle < 1e-6 {
+        return v;
+    }
+    v * (sin_half_angle.atan2(q.w) / sin_half_angle)
+}
+
+/// The exponential of the pure quaternion with vector part `v`
+fn quat_exp(v: Vec3) -> Quat {
+    let half_angle = v.length();
+    if half_angle < 1e-6 {
+        return Quat::from_xyzw(v.x, v.y, v.z, 1.0).normalize();
+    }
+    let axis = v * (half_angle.sin() / half_angle);
+    Quat::from_xyzw(axis.x, axis.y, axis.z, half_angle.cos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_arc() {
+        let q = Quat::rotation_arc(Vec3::X, 2.0 * Vec3::Y);
+        assert!((q * Vec3::X).abs_diff_eq(Vec3::Y, 1e-6));
+        assert!((q.angle_between(Quat::IDENTITY) - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+
+        let from = Vec3::new(1.0, 2.0, 3.0);
+        let to = Vec3::new(-3.0, 0.5, 1.0);
+        let q = Quat::rotation_arc(from, to);
+        assert!((q * from.normalize()).abs_diff_eq(to.normalize(), 1e-6));
+    }
+
+    #[test]
+    fn rotation_arc_aligned() {
+        assert_eq!(Quat::rotation_arc(Vec3::Z, 3.0 * Vec3::Z), Quat::IDENTITY);
+        assert_eq!(Quat::rotation_arc(Vec3::ZERO, Vec3::Z), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn rotation_arc_opposite() {
+        for from in [Vec3::X, Vec3::Y, Vec3::Z, Vec3::new(1.0, -1.0, 0.5)].iter() {
+            let q = Quat::rotation_arc(*from, -*from);
+            assert!(q.is_finite());
+            assert!(q.is_normalized());
+            assert!((q * from.normalize()).abs_diff_eq(-from.normalize(), 1e-5));
+        }
+    }
+
+    fn assert_same_rotation(a: Quat, b: Quat) {
+        assert!(a.dot(b).abs() > 1.0 - 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn slerp_path_samples() {
+        use std::f32::consts::PI;
+
+        let keys = [
+            Quat::IDENTITY,
+            Quat::from_rotation_y(PI / 2.0),
+            Quat::from_rotation_y(PI / 2.0) * Quat::from_rotation_x(PI / 2.0),
+        ];
+        assert_same_rotation(slerp_path(&keys, 0.0), keys[0]);
+        assert_same_rotation(slerp_path(&keys, 0.5), keys[1]);
+        assert_same_rotation(slerp_path(&keys, 1.0), keys[2]);
+        assert_same_rotation(slerp_path(&keys, 0.25), Quat::from_rotation_y(PI / 4.0));
+        assert_same_rotation(slerp_path(&keys, -1.0), keys[0]);
+        assert_same_rotation(slerp_path(&keys, 2.0), keys[2]);
+
+        assert_eq!(slerp_path(&[], 0.5), Quat::IDENTITY);
+        assert_eq!(slerp_path(&keys[1..2], 0.5), keys[1]);
+    }
+
+    #[test]
+    fn slerp_path_takes_the_short_way() {
+        use std::f32::consts::PI;
+
+        // the second key is the same rotation with a flipped sign, which would make a naive slerp
+        // spin almost all the way around
+        let keys = [Quat::IDENTITY, -Quat::from_rotation_z(PI / 2.0)];
+        assert_same_rotation(slerp_path(&keys, 0.5), Quat::from_rotation_z(PI / 4.0));
+    }
+
+    #[test]
+    fn smooth_quat_path_samples() {
+        use std::f32::consts::PI;
+
+        let keys = [
+            Quat::IDENTITY,
+            -Quat::from_rotation_y(PI / 2.0),
+            Quat::from_rotation_y(PI / 2.0) * Quat::from_rotation_x(PI / 2.0),
+            Quat::from_rotation_x(PI / 3.0),
+        ];
+        for (i, key) in keys.iter().enumerate() {
+            assert_same_rotation(smooth_quat_path(&keys, i as f32 / 3.0), *key);
+        }
+
+        // samples stay normalized, and close to their neighbours
+        let mut previous = smooth_quat_path(&keys, 0.0);
+        for step in 1..=300 {
+            let current = smooth_quat_path(&keys, step as f32 / 300.0);
+            assert!(current.is_normalized());
+            assert!(current.dot(previous).abs() > 0.999);
+            previous = current;
+        }
+
+        // the sign flip on the second key doesn't cause a detour
+        assert_same_rotation(
+            smooth_quat_path(&keys[..2], 0.5),
+            Quat::from_rotation_y(PI / 4.0),
+        );
+    }
+
+    #[test]
+    fn quat_rotate_towards_limits_the_step() {
+        use std::f32::consts::PI;
+
+        let from = Quat::IDENTITY;
+        let to = Quat::from_rotation_y(PI / 2.0);
+        let step = quat_rotate_towards(from, to, PI / 6.0);
+        assert!(step.is_normalized());
+        assert!((step.angle_between(from) - PI / 6.0).abs() < 1e-5);
+        assert_same_rotation(step, Quat::from_rotation_y(PI / 6.0));
+
+        // three steps get there exactly, and further steps stay there
+        let mut current = from;
+        for _ in 0..4 {
+            current = quat_rotate_towards(current, to, PI / 6.0 + 1e-5);
+        }
+        assert_eq!(current, to);
+
+        assert_eq!(quat_rotate_towards(from, to, PI), to);
+        assert_eq!(quat_rotate_towards(from, to, 0.0), from);
+        assert_eq!(quat_rotate_towards(from, to, -1.0), from);
+    }
+
+    #[test]
+    fn quat_rotate_towards_edge_cases() {
+        use std::f32::consts::PI;
+
+        // the same rotation with the opposite sign is already there
+        let q = Quat::from_rotation_x(0.3);
+        assert_eq!(quat_rotate_towards(q, -q, 0.1), -q);
+        assert_eq!(quat_rotate_towards(q, q, 0.1), q);
+
+        // a half turn away takes the given step without producing NaN; both directions are
+        // equally short, so only the angles are checked
+        let from = Quat::from_rotation_z(0.2);
+        let to = from * Quat::from_rotation_x(PI);
+        let step = quat_rotate_towards(from, to, PI / 4.0);
+        assert!(step.is_finite());
+        assert!(step.is_normalized());
+        assert!((step.angle_between(from) - PI / 4.0).abs() < 1e-3);
+        assert!((step.angle_between(to) - 3.0 * PI / 4.0).abs() < 1e-3);
+
+        // the long way round is never taken
+        let to = -Quat::from_rotation_z(PI / 2.0);
+        let step = quat_rotate_towards(Quat::IDENTITY, to, PI / 4.0);
+        assert_same_rotation(step, Quat::from_rotation_z(PI / 4.0));
+    }
+
+    #[test]
+    fn quat_looking_to_faces_forward() {
+        let up_vectors = [Vec3::Y, Vec3::new(0.3, 2.0, -0.5)];
+        let forwards = [
+            -Vec3::Z,
+            Vec3::X,
+            Vec3::new(1.0, -2.0, 3.0),
+            Vec3::new(-0.2, 0.1, -5.0),
+        ];
+        for &up in up_vectors.iter() {
+            for &forward in forwards.iter() {
+                let q = quat_looking_to(forward, up);
+                assert!(q.is_normalized());
+                assert!((q * -Vec3::Z).abs_diff_eq(forward.normalize(), 1e-5));
+                // the local up axis is perpendicular to forward and leans towards `up`
+                let local_up = q * Vec3::Y;
+                assert!(local_up.dot(forward).abs() < 1e-5);
+                assert!(local_up.dot(up) > 0.0);
+                assert!((q * Vec3::X).dot(up).abs() < 1e-5);
+            }
+        }
+        assert_eq!(quat_looking_to(-Vec3::Z, Vec3::Y), Quat::IDENTITY);
+        assert_eq!(quat_looking_to(Vec3::ZERO, Vec3::Y), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn quat_looking_to_degenerate_up() {
+        for &(forward, up) in [
+            (Vec3::Y, Vec3::Y),
+            (-Vec3::Y, Vec3::Y),
+            (Vec3::X, -Vec3::X),
+            (Vec3::new(0.0, 0.0, 2.0), Vec3::Z),
+            (Vec3::new(1.0, 2.0, 3.0), Vec3::ZERO),
+        ]
+        .iter()
+        {
+            let q = quat_looking_to(forward, up);
+            assert!(q.is_finite(), "{:?}", (forward, up));
+            assert!(q.is_normalized(), "{:?}", (forward, up));
+            assert!(
+                (q * -Vec3::Z).abs_diff_eq(forward.normalize(), 1e-5),
+                "{:?}",
+                (forward, up)
+            );
+        }
+    }
+
+    #[test]
+    fn quat_looking_at_matches_face_toward() {
+        use crate::{FaceToward, Mat4};
+
+        let eye = Vec3::new(50.0, 60.0, 0.0);
+        let target = Vec3::new(1.0, -2.0, 3.0);
+        let q = quat_looking_at(eye, target, Vec3::Y);
+        let matrix = Mat4::face_toward(eye, target, Vec3::Y);
+        assert!((q * Vec3::X).abs_diff_eq(matrix.x_axis.truncate(), 1e-5));
+        assert!((q * Vec3::Y).abs_diff_eq(matrix.y_axis.truncate(), 1e-5));
+        assert!((q * Vec3::Z).abs_diff_eq(matrix.z_axis.truncate(), 1e-5));
+
+        assert_eq!(quat_looking_at(eye, eye, Vec3::Y), Quat::IDENTITY);
+    }
+}
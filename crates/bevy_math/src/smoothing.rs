@@ -0,0 +1,315 @@
+use crate::{Vec2, Vec3};
+use std::ops::{Add, Mul, Sub};
+
+/// The values the smoothing functions in this module work on
+trait Smoothable: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self> {
+    const ZERO: Self;
+
+    fn length(self) -> f32;
+    fn dot(self, other: Self) -> f32;
+}
+
+impl Smoothable for f32 {
+    const ZERO: Self = 0.0;
+
+    fn length(self) -> f32 {
+        self.abs()
+    }
+
+    fn dot(self, other: Self) -> f32 {
+        self * other
+    }
+}
+
+impl Smoothable for Vec2 {
+    const ZERO: Self = Vec2::ZERO;
+
+    fn length(self) -> f32 {
+        Vec2::length(self)
+    }
+
+    fn dot(self, other: Self) -> f32 {
+        Vec2::dot(self, other)
+    }
+}
+
+impl Smoothable for Vec3 {
+    const ZERO: Self = Vec3::ZERO;
+
+    fn length(self) -> f32 {
+        Vec3::length(self)
+    }
+
+    fn dot(self, other: Self) -> f32 {
+        Vec3::dot(self, other)
+    }
+}
+
+/// Moves `current` in a straight line towards `target`, by at most `max_delta`.
+///
+/// The result never overshoots: `target` itself is returned once it is within `max_delta`. A
+/// negative `max_delta` leaves `current` where it is.
+pub fn move_towards(current: Vec3, target: Vec3, max_delta: f32) -> Vec3 {
+    move_towards_generic(current, target, max_delta)
+}
+
+/// Moves `current` in a straight line towards `target`, by at most `max_delta`.
+///
+/// See [`move_towards`].
+pub fn move_towards_vec2(current: Vec2, target: Vec2, max_delta: f32) -> Vec2 {
+    move_towards_generic(current, target, max_delta)
+}
+
+/// Moves `current` towards `target`, by at most `max_delta`.
+///
+/// See [`move_towards`].
+pub fn move_towards_f32(current: f32, target: f32, max_delta: f32) -> f32 {
+    move_towards_generic(current, target, max_delta)
+}
+
+fn move_towards_generic<T: Smoothable>(current: T, target: T, max_delta: f32) -> T {
+    let delta = target - current;
+    let distance = delta.length();
+    if distance <= max_delta.max(0.0) {
+        target
+    } else if max_delta <= 0.0 {
+        current
+    } else {
+        current + delta * (max_delta / distance)
+    }
+}
+
+/// Smoothly moves `current` towards `target` with a critically damped spring, which never
+/// oscillates around the target.
+///
+/// `velocity` carries the state of the spring between calls and is updated in place: start it at
+/// zero and keep passing the same value every frame. `smooth_time` is roughly the time it takes to
+/// reach the target, and `dt` is the time elapsed since the previous call. The result is frame rate
+/// independent and stays stable for large `dt`, where it lands on the target without overshooting.
+pub fn smooth_damp(
+    current: Vec3,
+    target: Vec3,
+    velocity: &mut Vec3,
+    smooth_time: f32,
+    dt: f32,
+) -> Vec3 {
+    smooth_damp_generic(current, target, velocity, smooth_time, dt)
+}
+
+/// Smoothly moves `current` towards `target` with a critically damped spring.
+///
+/// See [`smooth_damp`].
+pub fn smooth_damp_vec2(
+    current: Vec2,
+    target: Vec2,
+    velocity: &mut Vec2,
+    smooth_time: f32,
+    dt: f32,
+) -> Vec2 {
+    smooth_damp_generic(current, target, velocity, smooth_time, dt)
+}
+
+/// Smoothly moves `current` towards `target` with a critically damped spring.
+///
+/// See [`smooth_damp`].
+pub fn smooth_damp_f32(
+    current: f32,
+    target: f32,
+    velocity: &mut f32,
+    smooth_time: f32,
+    dt: f32,
+) -> f32 {
+    smooth_damp_generic(current, target, velocity, smooth_time, dt)
+}
+
+fn smooth_damp_generic<T: Smoothable>(
+    current: T,
+    target: T,
+    velocity: &mut T,
+    smooth_time: f32,
+    dt: f32,
+) -> T {
+    if dt <= 0.0 {
+        return current;
+    }
+
+    // closed form of the critically damped spring, using a cheap approximation of exp(-x) which
+    // stays within (0, 1] for any step size
+    let omega = 2.0 / smooth_time.max(1e-4);
+    let x = omega * dt;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    let offset = current - target;
+    let step = (*velocity + offset * omega) * dt;
+    *velocity = (*velocity - step * omega) * decay;
+    let output = target + (offset + step) * decay;
+
+    // a large step can carry the approximation past the target: stop there instead
+    if (target - current).dot(output - target) > 0.0 {
+        *velocity = T::ZERO;
+        return target;
+    }
+    output
+}
+
+/// Exponentially moves `current` towards `target`, closing the same fraction of the remaining
+/// distance every second.
+///
+/// This is the frame rate independent version of the common `current.lerp(target, 0.1)` called
+/// once per frame. Such a lerp closes 10% of the distance every frame, so it converges twice as
+/// fast at 120 fps as at 60 fps. Here the remaining distance is multiplied by
+/// `exp(-decay_rate * dt)`, and since `exp(-k * a) * exp(-k * b) == exp(-k * (a + b))`, splitting
+/// the same time into more, smaller steps gives the same result. `decay_rate` is how fast the
+/// distance shrinks: it is divided by `e` every `1.0 / decay_rate` seconds. The result never
+/// overshoots the target, whatever the value of `dt`.
+pub fn exp_decay(current: Vec3, target: Vec3, decay_rate: f32, dt: f32) -> Vec3 {
+    exp_decay_generic(current, target, decay_rate, dt)
+}
+
+/// Exponentially moves `current` towards `target`, in a frame rate independent way.
+///
+/// See [`exp_decay`].
+pub fn exp_decay_vec2(current: Vec2, target: Vec2, decay_rate: f32, dt: f32) -> Vec2 {
+    exp_decay_generic(current, target, decay_rate, dt)
+}
+
+/// Exponentially moves `current` towards `target`, in a frame rate independent way.
+///
+/// See [`exp_decay`].
+pub fn exp_decay_f32(current: f32, target: f32, decay_rate: f32, dt: f32) -> f32 {
+    exp_decay_generic(current, target, decay_rate, dt)
+}
+
+fn exp_decay_generic<T: Smoothable>(current: T, target: T, decay_rate: f32, dt: f32) -> T {
+    target + (current - target) * (-decay_rate * dt).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_towards_steps() {
+        let current = Vec3::new(1.0, 2.0, 3.0);
+        let target = Vec3::new(1.0, 2.0, 13.0);
+        assert!(move_towards(current, target, 4.0).abs_diff_eq(Vec3::new(1.0, 2.0, 7.0), 1e-6));
+        assert_eq!(move_towards(current, target, 0.0), current);
+        assert_eq!(move_towards(current, target, -1.0), current);
+
+        let step = move_towards_vec2(Vec2::ZERO, Vec2::new(3.0, 4.0), 2.5);
+        assert!(step.abs_diff_eq(Vec2::new(1.5, 2.0), 1e-6));
+
+        assert_eq!(move_towards_f32(10.0, 0.0, 3.0), 7.0);
+        assert_eq!(move_towards_f32(10.0, 20.0, 3.0), 13.0);
+    }
+
+    #[test]
+    fn move_towards_does_not_overshoot() {
+        let current = Vec3::new(1.0, 2.0, 3.0);
+        let target = Vec3::new(2.0, 2.0, 3.0);
+        assert_eq!(move_towards(current, target, 1.0), target);
+        assert_eq!(move_towards(current, target, 100.0), target);
+        assert_eq!(
+            move_towards_vec2(Vec2::ZERO, Vec2::ONE, f32::INFINITY),
+            Vec2::ONE
+        );
+        assert_eq!(move_towards_f32(0.0, 0.5, 1.0), 0.5);
+
+        // repeated steps land exactly on the target and stay there
+        let mut position = Vec3::ZERO;
+        let target = Vec3::new(3.0, -4.0, 12.0);
+        for _ in 0..20 {
+            position = move_towards(position, target, 1.0);
+        }
+        assert_eq!(position, target);
+    }
+
+    #[test]
+    fn move_towards_zero_distance() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(move_towards(v, v, 1.0), v);
+        assert_eq!(move_towards(v, v, 0.0), v);
+        assert_eq!(move_towards(v, v, -1.0), v);
+    }
+
+    #[test]
+    fn smooth_damp_converges_monotonically() {
+        let target = Vec3::new(10.0, -5.0, 2.0);
+        for &dt in [1.0 / 144.0, 1.0 / 60.0, 0.1, 1.0, 100.0].iter() {
+            let mut position = Vec3::ZERO;
+            let mut velocity = Vec3::ZERO;
+            let mut distance = position.distance(target);
+            for _ in 0..(10.0 / dt) as usize + 1 {
+                position = smooth_damp(position, target, &mut velocity, 0.3, dt);
+                assert!(position.is_finite() && velocity.is_finite(), "dt = {}", dt);
+                let new_distance = position.distance(target);
+                assert!(new_distance <= distance, "dt = {}", dt);
+                distance = new_distance;
+            }
+            assert!(distance < 1e-3, "dt = {}: {}", dt, distance);
+            assert!(velocity.length() < 1e-2, "dt = {}: {:?}", dt, velocity);
+        }
+    }
+
+    #[test]
+    fn smooth_damp_is_frame_rate_independent() {
+        let run = |dt: f32| {
+            let mut position = Vec2::ZERO;
+            let mut velocity = Vec2::ZERO;
+            for _ in 0..(0.5 / dt).round() as usize {
+                position = smooth_damp_vec2(position, Vec2::new(4.0, 3.0), &mut velocity, 0.5, dt);
+            }
+            position
+        };
+        assert!(run(1.0 / 30.0).abs_diff_eq(run(1.0 / 240.0), 1e-2));
+    }
+
+    #[test]
+    fn smooth_damp_f32_edge_cases() {
+        let mut velocity = 0.0;
+        assert_eq!(smooth_damp_f32(1.0, 5.0, &mut velocity, 0.2, 0.0), 1.0);
+        assert_eq!(velocity, 0.0);
+
+        // an enormous step lands on the target instead of overshooting
+        assert_eq!(smooth_damp_f32(1.0, 5.0, &mut velocity, 0.2, 1e6), 5.0);
+        assert!(velocity.abs() < 1e-6);
+
+        // an existing velocity away from the target is absorbed without oscillating
+        let mut position = 0.0;
+        let mut velocity = -20.0;
+        let mut crossed = false;
+        for _ in 0..600 {
+            position = smooth_damp_f32(position, 1.0, &mut velocity, 0.25, 1.0 / 60.0);
+            crossed |= position > 1.0;
+        }
+        assert!(!crossed);
+        assert!((position - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn exp_decay_is_frame_rate_independent() {
+        let run = |dt: f32| {
+            let mut position = Vec3::ZERO;
+            for _ in 0..(1.0 / dt).round() as usize {
+                position = exp_decay(position, Vec3::new(4.0, -3.0, 12.0), 3.0, dt);
+            }
+            position
+        };
+        let expected = Vec3::new(4.0, -3.0, 12.0) * (1.0 - (-3.0f32).exp());
+        assert!(run(1.0 / 30.0).abs_diff_eq(expected, 1e-4));
+        assert!(run(1.0 / 144.0).abs_diff_eq(expected, 1e-4));
+        assert!(run(1.0 / 30.0).abs_diff_eq(run(1.0 / 144.0), 1e-4));
+    }
+
+    #[test]
+    fn exp_decay_edge_cases() {
+        assert_eq!(exp_decay_f32(1.0, 5.0, 2.0, 0.0), 1.0);
+        assert_eq!(exp_decay_f32(1.0, 5.0, 0.0, 1.0), 1.0);
+        assert_eq!(exp_decay_f32(1.0, 5.0, 2.0, f32::INFINITY), 5.0);
+        assert_eq!(exp_decay_f32(5.0, 5.0, 2.0, 0.1), 5.0);
+
+        // a large step lands close to the target without overshooting it
+        let position = exp_decay_vec2(Vec2::ZERO, Vec2::new(3.0, 4.0), 10.0, 10.0);
+        assert!(position.abs_diff_eq(Vec2::new(3.0, 4.0), 1e-6));
+        assert!(position.x <= 3.0 && position.y <= 4.0);
+    }
+}
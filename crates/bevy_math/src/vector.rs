@@ -0,0 +1,187 @@
+use crate::{Mat4, Vec3, Vec3A};
+
+/// Returns a tangent and a bitangent which, together with the unit length `normal`, form a right
+/// handed orthonormal basis: `tangent.cross(bitangent) == normal`.
+///
+/// This uses the branchless construction from Duff et al., "Building an Orthonormal Basis,
+/// Revisited", which stays numerically stable for every direction, including the poles where
+/// crossing with a fixed "up" vector degenerates.
+pub fn orthonormal_basis(normal: Vec3) -> (Vec3, Vec3) {
+    debug_assert!(normal.is_normalized(), "normal must be normalized");
+    let sign = 1.0f32.copysign(normal.z);
+    let a = -1.0 / (sign + normal.z);
+    let b = normal.x * normal.y * a;
+    (
+        Vec3::new(
+            1.0 + sign * normal.x * normal.x * a,
+            sign * b,
+            -sign * normal.x,
+        ),
+        Vec3::new(b, sign + normal.y * normal.y * a, -normal.y),
+    )
+}
+
+/// Returns an arbitrary unit length vector orthogonal to the unit length vector `v`.
+///
+/// See [`orthonormal_basis`] for the construction used.
+pub fn any_orthonormal_vector(v: Vec3) -> Vec3 {
+    orthonormal_basis(v).1
+}
+
+/// Spherically interpolates between the unit length directions `a` and `b`, along the great arc
+/// joining them, so the result stays unit length and turns at a constant rate as `t` goes from
+/// `0.0` to `1.0`.
+///
+/// Nearly parallel directions fall back to a normalized linear interpolation. Opposite directions
+/// have no single great arc between them: an arbitrary one is picked, using
+/// [`any_orthonormal_vector`].
+pub fn vec3_slerp(a: Vec3, b: Vec3, t: f32) -> Vec3 {
+    debug_assert!(a.is_normalized(), "a must be normalized");
+    debug_assert!(b.is_normalized(), "b must be normalized");
+    let cos_angle = a.dot(b).clamp(-1.0, 1.0);
+    if cos_angle > 1.0 - 1e-4 {
+        return a.lerp(b, t).normalize();
+    }
+
+    // rotate `a` in the plane it spans with `b`
+    let perpendicular = b - a * cos_angle;
+    let (perpendicular, angle) = if perpendicular.length_squared() > 1e-8 {
+        (perpendicular.normalize(), cos_angle.acos())
+    } else {
+        (any_orthonormal_vector(a), std::f32::consts::PI)
+    };
+    let angle = angle * t;
+    a * angle.cos() + perpendicular * angle.sin()
+}
+
+/// Transforms each of the `points` by `matrix`, lazily.
+///
+/// When transforming many points, prefer [`transform_points_vec3a`] if the points can be stored
+/// as [`Vec3A`]: its 16 byte alignment lets the whole transform run on SIMD registers.
+pub fn transform_points<I: IntoIterator<Item = Vec3>>(
+    matrix: &Mat4,
+    points: I,
+) -> impl Iterator<Item = Vec3> {
+    let matrix = *matrix;
+    points
+        .into_iter()
+        .map(move |point| matrix.transform_point3(point))
+}
+
+/// Transforms each of the `points` by `matrix`, lazily.
+///
+/// See [`transform_points`].
+pub fn transform_points_vec3a<I: IntoIterator<Item = Vec3A>>(
+    matrix: &Mat4,
+    points: I,
+) -> impl Iterator<Item = Vec3A> {
+    let matrix = *matrix;
+    points
+        .into_iter()
+        .map(move |point| matrix.transform_point3a(point))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_basis(normal: Vec3) {
+        let (tangent, bitangent) = orthonormal_basis(normal);
+        assert!(tangent.is_normalized(), "{:?}", normal);
+        assert!(bitangent.is_normalized(), "{:?}", normal);
+        assert!(tangent.dot(normal).abs() < 1e-6, "{:?}", normal);
+        assert!(bitangent.dot(normal).abs() < 1e-6, "{:?}", normal);
+        assert!(tangent.dot(bitangent).abs() < 1e-6, "{:?}", normal);
+        assert!(
+            tangent.cross(bitangent).abs_diff_eq(normal, 1e-6),
+            "{:?}",
+            normal
+        );
+    }
+
+    #[test]
+    fn orthonormal_basis_axes() {
+        for normal in [Vec3::X, Vec3::Y, Vec3::Z, -Vec3::X, -Vec3::Y, -Vec3::Z].iter() {
+            assert_basis(*normal);
+        }
+    }
+
+    #[test]
+    fn orthonormal_basis_near_poles() {
+        assert_basis(Vec3::new(1e-7, 1e-7, 1.0).normalize());
+        assert_basis(Vec3::new(1e-4, -1e-4, -1.0).normalize());
+        assert_basis(Vec3::new(0.0, 1e-6, -1.0).normalize());
+        assert_basis(Vec3::new(1.0, 2.0, -3.0).normalize());
+    }
+
+    #[test]
+    fn any_orthonormal_vector_is_orthonormal() {
+        let v = Vec3::new(-2.0, 0.5, 1.0).normalize();
+        let o = any_orthonormal_vector(v);
+        assert!(o.is_normalized());
+        assert!(o.dot(v).abs() < 1e-6);
+    }
+
+    #[test]
+    fn vec3_slerp_orthogonal() {
+        let halfway = vec3_slerp(Vec3::X, Vec3::Y, 0.5);
+        assert!(halfway.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0).normalize(), 1e-6));
+        assert!(vec3_slerp(Vec3::X, Vec3::Y, 0.0).abs_diff_eq(Vec3::X, 1e-6));
+        assert!(vec3_slerp(Vec3::X, Vec3::Y, 1.0).abs_diff_eq(Vec3::Y, 1e-6));
+
+        // constant angular speed
+        let third = vec3_slerp(Vec3::X, Vec3::Z, 1.0 / 3.0);
+        let angle = std::f32::consts::FRAC_PI_6;
+        assert!(third.abs_diff_eq(Vec3::new(angle.cos(), 0.0, angle.sin()), 1e-6));
+    }
+
+    #[test]
+    fn vec3_slerp_nearly_parallel() {
+        let a = Vec3::new(1.0, 2.0, 3.0).normalize();
+        let b = Vec3::new(1.0, 2.0, 3.001).normalize();
+        for i in 0..=4 {
+            let v = vec3_slerp(a, b, i as f32 / 4.0);
+            assert!(v.is_normalized());
+            assert!(v.abs_diff_eq(a, 1e-3));
+        }
+        assert!(vec3_slerp(a, a, 0.5).abs_diff_eq(a, 1e-6));
+    }
+
+    #[test]
+    fn vec3_slerp_antiparallel() {
+        for a in [Vec3::X, -Vec3::Z, Vec3::new(1.0, -2.0, 0.5).normalize()].iter() {
+            let halfway = vec3_slerp(*a, -*a, 0.5);
+            assert!(halfway.is_finite());
+            assert!(halfway.is_normalized());
+            assert!(halfway.dot(*a).abs() < 1e-6);
+            assert!(vec3_slerp(*a, -*a, 1.0).abs_diff_eq(-*a, 1e-5));
+        }
+
+        // nearly opposite directions still take the arc through `b`
+        let b = Vec3::new(-1.0, 0.01, 0.0).normalize();
+        let halfway = vec3_slerp(Vec3::X, b, 0.5);
+        assert!(halfway.abs_diff_eq(Vec3::Y, 1e-2));
+        assert!(vec3_slerp(Vec3::X, b, 1.0).abs_diff_eq(b, 1e-5));
+    }
+
+    #[test]
+    fn transform_points_matches_matrix() {
+        let matrix = Mat4::from_scale_rotation_translation(
+            Vec3::new(1.0, 2.0, 3.0),
+            crate::Quat::from_rotation_z(0.5),
+            Vec3::new(-1.0, 4.0, 0.5),
+        );
+        let points = [Vec3::ZERO, Vec3::X, Vec3::new(1.0, -2.0, 3.0)];
+        let transformed = transform_points(&matrix, points.iter().copied()).collect::<Vec<_>>();
+        let transformed_vec3a =
+            transform_points_vec3a(&matrix, points.iter().map(|p| Vec3A::from(*p)))
+                .collect::<Vec<_>>();
+        assert_eq!(transformed.len(), points.len());
+        for ((point, transformed), transformed_vec3a) in
+            points.iter().zip(transformed).zip(transformed_vec3a)
+        {
+            assert_eq!(transformed, matrix.transform_point3(*point));
+            assert!(Vec3::from(transformed_vec3a).abs_diff_eq(transformed, 1e-6));
+        }
+    }
+}
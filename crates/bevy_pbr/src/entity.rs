@@ -5,7 +5,7 @@ use bevy_render::{
     draw::Draw,
     mesh::Mesh,
     pipeline::{RenderPipeline, RenderPipelines},
-    prelude::Visible,
+    prelude::{ComputedVisibility, Visible},
     render_graph::base::MainPass,
 };
 use bevy_transform::prelude::{GlobalTransform, Transform};
@@ -18,6 +18,7 @@ pub struct PbrBundle {
     pub main_pass: MainPass,
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
@@ -34,6 +35,7 @@ impl Default for PbrBundle {
             material: Default::default(),
             main_pass: Default::default(),
             draw: Default::default(),
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -1,5 +1,9 @@
 use super::{Camera, DepthCalculation};
-use crate::{draw::OutsideFrustum, prelude::Visible};
+use crate::{
+    draw::OutsideFrustum,
+    prelude::Visible,
+    visibility::{self, ComputedVisibility},
+};
 use bevy_core::FloatOrd;
 use bevy_ecs::{entity::Entity, query::Without, reflect::ReflectComponent, system::Query};
 use bevy_reflect::Reflect;
@@ -132,6 +136,89 @@ impl RenderLayers {
     }
 }
 
+/// Hides an entity from cameras farther away than the given distance, in world units.
+///
+/// The distance is measured from the camera's translation to the entity's [`GlobalTransform`]
+/// translation, whatever the camera's [`DepthCalculation`]. Entities without a
+/// [`GlobalTransform`] are never culled by distance. This is independent of frustum culling:
+/// it still applies when frustum culling is disabled. A negative distance hides the entity from
+/// every camera.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct MaxVisibleDistance(pub f32);
+
+/// Defaults to an infinite distance, which never culls anything.
+impl Default for MaxVisibleDistance {
+    fn default() -> Self {
+        MaxVisibleDistance(f32::INFINITY)
+    }
+}
+
+/// Limits the distances at which an entity is visible, fading it in and out at the edges. This is
+/// the building block for levels of detail: give each level a range, with overlapping margins so
+/// that one fades in while the next fades out.
+///
+/// The entity is culled when it is closer to the camera than `start`, or farther than `end`. It
+/// is fully visible between `start + start_margin` and `end - end_margin`, and its fade factor
+/// goes linearly from `0.0` to `1.0` across each margin: add a
+/// [`VisibilityRangeFade`](crate::visibility::VisibilityRangeFade) to read it. Distances are
+/// measured the same way as for [`MaxVisibleDistance`], which still applies on top of this.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct VisibilityRange {
+    /// The distance under which the entity is culled
+    pub start: f32,
+    /// The width of the band after `start` in which the entity fades in
+    pub start_margin: f32,
+    /// The distance over which the entity is culled
+    pub end: f32,
+    /// The width of the band before `end` in which the entity fades out
+    pub end_margin: f32,
+}
+
+/// Defaults to the whole `0.0..INFINITY` range, without any fading.
+impl Default for VisibilityRange {
+    fn default() -> Self {
+        VisibilityRange::new(0.0, f32::INFINITY)
+    }
+}
+
+impl VisibilityRange {
+    /// Creates a range that cuts off abruptly, without fading
+    pub fn new(start: f32, end: f32) -> Self {
+        VisibilityRange {
+            start,
+            start_margin: 0.0,
+            end,
+            end_margin: 0.0,
+        }
+    }
+
+    /// Returns true if an entity at `distance` from the camera isn't culled
+    pub fn contains(&self, distance: f32) -> bool {
+        self.start <= distance && distance <= self.end
+    }
+
+    /// Returns how faded in an entity at `distance` from the camera is, from `0.0` when it is
+    /// culled to `1.0` when it is outside of both margins.
+    pub fn fade(&self, distance: f32) -> f32 {
+        if !self.contains(distance) {
+            return 0.0;
+        }
+        let fade_in = if self.start_margin > 0.0 {
+            (distance - self.start) / self.start_margin
+        } else {
+            1.0
+        };
+        let fade_out = if self.end_margin > 0.0 {
+            (self.end - distance) / self.end_margin
+        } else {
+            1.0
+        };
+        fade_in.min(fade_out).clamp(0.0, 1.0)
+    }
+}
+
 #[cfg(test)]
 mod rendering_mask_tests {
     use super::{Layer, RenderLayers};
@@ -195,6 +282,7 @@ mod rendering_mask_tests {
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn visible_entities_system(
     mut camera_query: Query<(
         &Camera,
@@ -202,7 +290,17 @@ pub fn visible_entities_system(
         &mut VisibleEntities,
         Option<&RenderLayers>,
     )>,
-    visible_query: Query<(Entity, &Visible, Option<&RenderLayers>), Without<OutsideFrustum>>,
+    visible_query: Query<
+        (
+            Entity,
+            &Visible,
+            Option<&ComputedVisibility>,
+            Option<&RenderLayers>,
+            Option<&MaxVisibleDistance>,
+            Option<&VisibilityRange>,
+        ),
+        Without<OutsideFrustum>,
+    >,
     visible_transform_query: Query<&GlobalTransform, Without<OutsideFrustum>>,
 ) {
     for (camera, camera_global_transform, mut visible_entities, maybe_camera_mask) in
@@ -214,8 +312,16 @@ pub fn visible_entities_system(
 
         let mut no_transform_order = 0.0;
         let mut transparent_entities = Vec::new();
-        for (entity, visible, maybe_entity_mask) in visible_query.iter() {
-            if !visible.is_visible {
+        for (
+            entity,
+            visible,
+            maybe_computed_visibility,
+            maybe_entity_mask,
+            maybe_max_distance,
+            maybe_visibility_range,
+        ) in visible_query.iter()
+        {
+            if !visibility::is_visible(visible, maybe_computed_visibility) {
                 continue;
             }
 
@@ -224,8 +330,28 @@ pub fn visible_entities_system(
                 continue;
             }
 
-            let order = if let Ok(global_transform) = visible_transform_query.get(entity) {
-                let position = global_transform.translation;
+            let maybe_position = visible_transform_query
+                .get(entity)
+                .ok()
+                .map(|global_transform| global_transform.translation);
+
+            if let (Some(position), Some(max_distance)) = (maybe_position, maybe_max_distance) {
+                // comparing the distances themselves rather than their squares, so that a negative
+                // maximum doesn't act like its absolute value
+                if camera_position.distance(position) > max_distance.0 {
+                    continue;
+                }
+            }
+
+            if let (Some(position), Some(visibility_range)) =
+                (maybe_position, maybe_visibility_range)
+            {
+                if !visibility_range.contains(camera_position.distance(position)) {
+                    continue;
+                }
+            }
+
+            let order = if let Some(position) = maybe_position {
                 // smaller distances are sorted to lower indices by using the distance from the
                 // camera
                 FloatOrd(match camera.depth_calculation {
@@ -248,7 +374,8 @@ pub fn visible_entities_system(
         // sort opaque entities front-to-back
         visible_entities.value.sort_by_key(|e| e.order);
 
-        // sort transparent entities front-to-back
+        // sort transparent entities back-to-front, so they blend correctly. both sorts are stable,
+        // so equidistant entities keep the order they were queried in
         transparent_entities.sort_by_key(|e| -e.order);
         visible_entities.value.extend(transparent_entities);
 
@@ -256,3 +383,187 @@ pub fn visible_entities_system(
         // to prevent holding unneeded memory
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+    use bevy_math::Vec3;
+
+    fn visible_entities(world: &mut World, camera: Entity) -> Vec<Entity> {
+        let mut stage = SystemStage::single(visible_entities_system.system());
+        stage.run(world);
+        world
+            .get::<VisibleEntities>(camera)
+            .unwrap()
+            .iter()
+            .map(|visible_entity| visible_entity.entity)
+            .collect()
+    }
+
+    #[test]
+    fn max_visible_distance_cutoff() {
+        let mut world = World::default();
+        let camera = world
+            .spawn()
+            .insert_bundle((
+                Camera::default(),
+                GlobalTransform::identity(),
+                VisibleEntities::default(),
+            ))
+            .id();
+
+        let mut spawn_at = |distance: f32, max_distance: Option<f32>| {
+            let mut entity = world.spawn();
+            entity.insert_bundle((
+                Visible::default(),
+                GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -distance)),
+            ));
+            if let Some(max_distance) = max_distance {
+                entity.insert(MaxVisibleDistance(max_distance));
+            }
+            entity.id()
+        };
+        let near = spawn_at(5.0, Some(10.0));
+        let at_cutoff = spawn_at(10.0, Some(10.0));
+        let far = spawn_at(20.0, Some(10.0));
+        let far_uncapped = spawn_at(1000.0, None);
+        let far_infinite = spawn_at(1000.0, Some(f32::INFINITY));
+        let near_negative = spawn_at(5.0, Some(-10.0));
+
+        let visible = visible_entities(&mut world, camera);
+        assert!(visible.contains(&near));
+        assert!(visible.contains(&at_cutoff));
+        assert!(!visible.contains(&far));
+        assert!(visible.contains(&far_uncapped));
+        assert!(visible.contains(&far_infinite));
+        assert!(!visible.contains(&near_negative));
+
+        // the distance follows the camera
+        world
+            .get_mut::<GlobalTransform>(camera)
+            .unwrap()
+            .translation = Vec3::new(0.0, 0.0, -20.0);
+        let visible = visible_entities(&mut world, camera);
+        assert!(!visible.contains(&near));
+        assert!(visible.contains(&far));
+    }
+
+    #[test]
+    fn visibility_range_cutoff() {
+        let mut world = World::default();
+        let camera = world
+            .spawn()
+            .insert_bundle((
+                Camera::default(),
+                GlobalTransform::identity(),
+                VisibleEntities::default(),
+            ))
+            .id();
+
+        let mut spawn_at = |distance: f32| {
+            world
+                .spawn()
+                .insert_bundle((
+                    Visible::default(),
+                    GlobalTransform::from_translation(Vec3::new(0.0, distance, 0.0)),
+                    VisibilityRange {
+                        start: 5.0,
+                        start_margin: 1.0,
+                        end: 20.0,
+                        end_margin: 2.0,
+                    },
+                ))
+                .id()
+        };
+        let too_close = spawn_at(4.0);
+        let fading_in = spawn_at(5.5);
+        let inside = spawn_at(10.0);
+        let fading_out = spawn_at(19.0);
+        let too_far = spawn_at(21.0);
+
+        let visible = visible_entities(&mut world, camera);
+        assert!(!visible.contains(&too_close));
+        assert!(visible.contains(&fading_in));
+        assert!(visible.contains(&inside));
+        assert!(visible.contains(&fading_out));
+        assert!(!visible.contains(&too_far));
+    }
+
+    #[test]
+    fn visibility_range_fade() {
+        let range = VisibilityRange {
+            start: 5.0,
+            start_margin: 2.0,
+            end: 20.0,
+            end_margin: 4.0,
+        };
+        assert_eq!(range.fade(4.0), 0.0);
+        assert_eq!(range.fade(5.0), 0.0);
+        assert_eq!(range.fade(6.0), 0.5);
+        assert_eq!(range.fade(7.0), 1.0);
+        assert_eq!(range.fade(16.0), 1.0);
+        assert_eq!(range.fade(19.0), 0.25);
+        assert_eq!(range.fade(20.0), 0.0);
+        assert_eq!(range.fade(25.0), 0.0);
+
+        // without margins, the entity is either fully visible or culled
+        let range = VisibilityRange::new(5.0, 20.0);
+        assert_eq!(range.fade(5.0), 1.0);
+        assert_eq!(range.fade(20.0), 1.0);
+        assert_eq!(range.fade(20.5), 0.0);
+        assert_eq!(VisibilityRange::default().fade(1e30), 1.0);
+    }
+
+    #[test]
+    fn sorted_by_distance() {
+        let mut world = World::default();
+        let camera = world
+            .spawn()
+            .insert_bundle((
+                Camera::default(),
+                GlobalTransform::identity(),
+                VisibleEntities::default(),
+            ))
+            .id();
+
+        let mut spawn_at = |distance: f32, is_transparent: bool| {
+            world
+                .spawn()
+                .insert_bundle((
+                    Visible {
+                        is_visible: true,
+                        is_transparent,
+                    },
+                    GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -distance)),
+                ))
+                .id()
+        };
+        let opaque_far = spawn_at(20.0, false);
+        let opaque_near = spawn_at(5.0, false);
+        let transparent_near = spawn_at(5.0, true);
+        let transparent_mid_a = spawn_at(10.0, true);
+        let transparent_far = spawn_at(20.0, true);
+        let transparent_mid_b = spawn_at(10.0, true);
+        let transparent_mid_c = spawn_at(10.0, true);
+
+        // opaque entities come first, front-to-back, then transparent ones back-to-front, with
+        // equidistant entities kept in spawn order
+        assert_eq!(
+            visible_entities(&mut world, camera),
+            vec![
+                opaque_near,
+                opaque_far,
+                transparent_far,
+                transparent_mid_a,
+                transparent_mid_b,
+                transparent_mid_c,
+                transparent_near,
+            ]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
         VisibleEntities,
     },
     pipeline::RenderPipelines,
-    prelude::Visible,
+    prelude::{ComputedVisibility, Visible},
     render_graph::base,
     Draw, Mesh,
 };
@@ -19,6 +19,7 @@ pub struct MeshBundle {
     pub mesh: Handle<Mesh>,
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub render_pipelines: RenderPipelines,
     pub main_pass: MainPass,
     pub transform: Transform,
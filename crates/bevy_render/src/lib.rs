@@ -10,6 +10,7 @@ pub mod render_graph;
 pub mod renderer;
 pub mod shader;
 pub mod texture;
+pub mod visibility;
 pub mod wireframe;
 
 use bevy_ecs::{
@@ -34,6 +35,7 @@ pub mod prelude {
         pipeline::RenderPipelines,
         shader::Shader,
         texture::Texture,
+        visibility::{ComputedVisibility, Visibility},
     };
 }
 
@@ -43,8 +45,9 @@ use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, AssetStage};
 use bevy_ecs::schedule::{StageLabel, SystemLabel};
 use camera::{
-    ActiveCameras, Camera, DepthCalculation, OrthographicProjection, PerspectiveProjection,
-    RenderLayers, ScalingMode, VisibleEntities, WindowOrigin,
+    ActiveCameras, Camera, DepthCalculation, MaxVisibleDistance, OrthographicProjection,
+    PerspectiveProjection, RenderLayers, ScalingMode, VisibilityRange, VisibleEntities,
+    WindowOrigin,
 };
 use pipeline::{
     IndexFormat, PipelineCompiler, PipelineDescriptor, PipelineSpecialization, PrimitiveTopology,
@@ -69,6 +72,7 @@ use texture::ImageTextureLoader;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum RenderSystem {
+    VisibilityPropagate,
     VisibleEntities,
 }
 
@@ -152,6 +156,11 @@ impl Plugin for RenderPlugin {
         .register_type::<DepthCalculation>()
         .register_type::<Draw>()
         .register_type::<Visible>()
+        .register_type::<ComputedVisibility>()
+        .register_type::<Visibility>()
+        .register_type::<visibility::VisibilityRangeFade>()
+        .add_event::<visibility::VisibilityChanged>()
+        .add_event::<visibility::ViewVisibilityChanged>()
         .register_type::<OutsideFrustum>()
         .register_type::<RenderPipelines>()
         .register_type::<OrthographicProjection>()
@@ -164,6 +173,8 @@ impl Plugin for RenderPlugin {
         .register_type::<IndexFormat>()
         .register_type::<PipelineSpecialization>()
         .register_type::<RenderLayers>()
+        .register_type::<MaxVisibleDistance>()
+        .register_type::<VisibilityRange>()
         .register_type::<ScalingMode>()
         .register_type::<VertexBufferLayout>()
         .register_type::<WindowOrigin>()
@@ -195,6 +206,14 @@ impl Plugin for RenderPlugin {
                 .system()
                 .before(RenderSystem::VisibleEntities),
         )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            visibility::visibility_propagate_system
+                .system()
+                .label(RenderSystem::VisibilityPropagate)
+                .after(TransformSystem::ParentUpdate)
+                .before(RenderSystem::VisibleEntities),
+        )
         .add_system_to_stage(
             CoreStage::PostUpdate,
             camera::visible_entities_system
@@ -202,6 +221,12 @@ impl Plugin for RenderPlugin {
                 .label(RenderSystem::VisibleEntities)
                 .after(TransformSystem::TransformPropagate),
         )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            visibility::view_visibility_system
+                .system()
+                .after(RenderSystem::VisibleEntities),
+        )
         .add_system_to_stage(
             RenderStage::RenderResource,
             shader::shader_update_system.system(),
@@ -2,8 +2,9 @@ use super::{PipelineDescriptor, PipelineSpecialization};
 use crate::{
     draw::{Draw, DrawContext, OutsideFrustum},
     mesh::{Indices, Mesh},
-    prelude::{Msaa, Visible},
+    prelude::{ComputedVisibility, Msaa, Visible},
     renderer::RenderResourceBindings,
+    visibility,
 };
 use bevy_asset::{Assets, Handle};
 use bevy_ecs::{
@@ -82,18 +83,27 @@ impl Default for RenderPipelines {
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn draw_render_pipelines_system(
     mut draw_context: DrawContext,
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
     msaa: Res<Msaa>,
     meshes: Res<Assets<Mesh>>,
     mut query: Query<
-        (&mut Draw, &mut RenderPipelines, &Handle<Mesh>, &Visible),
+        (
+            &mut Draw,
+            &mut RenderPipelines,
+            &Handle<Mesh>,
+            &Visible,
+            Option<&ComputedVisibility>,
+        ),
         Without<OutsideFrustum>,
     >,
 ) {
-    for (mut draw, mut render_pipelines, mesh_handle, visible) in query.iter_mut() {
-        if !visible.is_visible {
+    for (mut draw, mut render_pipelines, mesh_handle, visible, computed_visibility) in
+        query.iter_mut()
+    {
+        if !visibility::is_visible(visible, computed_visibility) {
             continue;
         }
 
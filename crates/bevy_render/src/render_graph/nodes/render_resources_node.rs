@@ -1,12 +1,12 @@
 use crate::{
     pipeline::RenderPipelines,
-    prelude::Visible,
+    prelude::{ComputedVisibility, Visible},
     render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
     renderer::{
         self, BufferInfo, BufferMapMode, BufferUsage, RenderContext, RenderResourceBinding,
         RenderResourceBindings, RenderResourceContext, RenderResourceHints,
     },
-    texture,
+    texture, visibility,
 };
 
 use bevy_app::EventReader;
@@ -434,9 +434,25 @@ fn render_resources_node_system<T: RenderResources>(
     mut entities_waiting_for_textures: Local<Vec<Entity>>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
     removed: RemovedComponents<T>,
+    // entities shown again through one of their ancestors need their uniforms written as well
     mut queries: QuerySet<(
-        Query<(Entity, &T, &Visible, &mut RenderPipelines), Or<(Changed<T>, Changed<Visible>)>>,
-        Query<(Entity, &T, &Visible, &mut RenderPipelines)>,
+        Query<
+            (
+                Entity,
+                &T,
+                &Visible,
+                Option<&ComputedVisibility>,
+                &mut RenderPipelines,
+            ),
+            Or<(Changed<T>, Changed<Visible>, Changed<ComputedVisibility>)>,
+        >,
+        Query<(
+            Entity,
+            &T,
+            &Visible,
+            Option<&ComputedVisibility>,
+            &mut RenderPipelines,
+        )>,
     )>,
 ) {
     let state = state.deref_mut();
@@ -444,7 +460,7 @@ fn render_resources_node_system<T: RenderResources>(
     let render_resource_context = &**render_resource_context;
     uniform_buffer_arrays.begin_update();
     // initialize uniform buffer arrays using the first RenderResources
-    if let Some((_, first, _, _)) = queries.q0_mut().iter_mut().next() {
+    if let Some((_, first, _, _, _)) = queries.q0_mut().iter_mut().next() {
         uniform_buffer_arrays.initialize(first, render_resource_context);
     }
 
@@ -454,7 +470,7 @@ fn render_resources_node_system<T: RenderResources>(
 
     // handle entities that were waiting for texture loads on the last update
     for entity in std::mem::take(&mut *entities_waiting_for_textures) {
-        if let Ok((entity, uniforms, _visible, mut render_pipelines)) =
+        if let Ok((entity, uniforms, _visible, _computed_visibility, mut render_pipelines)) =
             queries.q1_mut().get_mut(entity)
         {
             if !setup_uniform_texture_resources::<T>(
@@ -467,8 +483,10 @@ fn render_resources_node_system<T: RenderResources>(
         }
     }
 
-    for (entity, uniforms, visible, mut render_pipelines) in queries.q0_mut().iter_mut() {
-        if !visible.is_visible {
+    for (entity, uniforms, visible, computed_visibility, mut render_pipelines) in
+        queries.q0_mut().iter_mut()
+    {
+        if !visibility::is_visible(visible, computed_visibility) {
             continue;
         }
         uniform_buffer_arrays.prepare_uniform_buffers(entity, uniforms);
@@ -496,10 +514,10 @@ fn render_resources_node_system<T: RenderResources>(
                 // if the buffer array was resized, write all entities to the new buffer, otherwise
                 // only write changes
                 if resized {
-                    for (entity, uniforms, visible, mut render_pipelines) in
+                    for (entity, uniforms, visible, computed_visibility, mut render_pipelines) in
                         queries.q1_mut().iter_mut()
                     {
-                        if !visible.is_visible {
+                        if !visibility::is_visible(visible, computed_visibility) {
                             continue;
                         }
 
@@ -513,10 +531,10 @@ fn render_resources_node_system<T: RenderResources>(
                         );
                     }
                 } else {
-                    for (entity, uniforms, visible, mut render_pipelines) in
+                    for (entity, uniforms, visible, computed_visibility, mut render_pipelines) in
                         queries.q0_mut().iter_mut()
                     {
-                        if !visible.is_visible {
+                        if !visibility::is_visible(visible, computed_visibility) {
                             continue;
                         }
 
@@ -0,0 +1,1021 @@
+use crate::{
+    camera::{VisibilityRange, VisibleEntities},
+    draw::Visible,
+};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    entity::Entity,
+    event::EventWriter,
+    query::{Changed, Without},
+    reflect::ReflectComponent,
+    system::{Local, Query},
+    world::Mut,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize};
+use bevy_transform::components::{Children, GlobalTransform, Parent};
+use bevy_utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+/// How an entity's visibility relates to the visibility of its parent.
+///
+/// This is optional: entities without it behave as [`Visibility::Inherited`]. It is combined with
+/// [`Visible::is_visible`], which still hides the entity on its own when `false`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect_value(Component, PartialEq, Serialize, Deserialize)]
+pub enum Visibility {
+    /// Visible only when the parent is visible. Root entities are visible.
+    Inherited,
+    /// Always hidden, along with every descendant that inherits from it.
+    Hidden,
+    /// Always visible, even when the parent is hidden.
+    Visible,
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Visibility::Inherited
+    }
+}
+
+/// Converts the `is_visible` flag used before [`Visibility`] existed: `true` is
+/// [`Visibility::Inherited`] and `false` is [`Visibility::Hidden`].
+impl From<bool> for Visibility {
+    fn from(is_visible: bool) -> Self {
+        if is_visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        }
+    }
+}
+
+impl Visibility {
+    /// Resolves this setting against the computed visibility of the parent
+    pub fn resolve(self, parent_is_visible: bool) -> bool {
+        match self {
+            Visibility::Inherited => parent_is_visible,
+            Visibility::Hidden => false,
+            Visibility::Visible => true,
+        }
+    }
+}
+
+/// The visibility of an entity once the visibility of its ancestors has been taken into account.
+///
+/// This is written by [`visibility_propagate_system`]: an entity is only visible when its own
+/// [`Visible::is_visible`] is set and its [`Visibility`] resolves to visible, which by default
+/// means every one of its ancestors is visible as well. Set [`Visible`] or [`Visibility`] to hide
+/// or show an entity; this component should be treated as read-only. Systems that draw entities
+/// should check it through [`is_visible`].
+///
+/// Culling is tracked separately in `is_visible_in_view`, so an entity that doesn't show up can be
+/// told apart from one that was hidden.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ComputedVisibility {
+    /// Whether the entity and its ancestors are visible
+    pub is_visible: bool,
+    /// Whether the entity made it into the [`VisibleEntities`] of at least one camera, after
+    /// frustum, distance and render layer culling. Written by [`view_visibility_system`].
+    pub is_visible_in_view: bool,
+}
+
+impl Default for ComputedVisibility {
+    fn default() -> Self {
+        ComputedVisibility {
+            is_visible: true,
+            is_visible_in_view: false,
+        }
+    }
+}
+
+/// How faded in an entity is because of its [`VisibilityRange`], from `0.0` to `1.0`, for shaders
+/// to dither it in and out. Written by [`view_visibility_system`] on entities that have this
+/// component: it isn't added automatically.
+///
+/// This is the largest fade of any camera the entity is in view of, `0.0` when it is in view of
+/// none, and always `1.0` without a [`VisibilityRange`]. It is kept apart from
+/// [`ComputedVisibility`] because it changes on every frame the entity spends in a fade margin.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct VisibilityRangeFade(pub f32);
+
+impl Default for VisibilityRangeFade {
+    fn default() -> Self {
+        VisibilityRangeFade(1.0)
+    }
+}
+
+/// An event sent when the [`ComputedVisibility`] of an entity is first computed, and then each
+/// time it flips. Culling is reported by [`ViewVisibilityChanged`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisibilityChanged {
+    pub entity: Entity,
+    /// The new value of [`ComputedVisibility::is_visible`]
+    pub is_visible: bool,
+}
+
+/// An event sent when [`ComputedVisibility::is_visible_in_view`] is first computed for an entity,
+/// and then each time it flips: when the entity enters the view of its first camera, or leaves the
+/// view of its last one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewVisibilityChanged {
+    pub entity: Entity,
+    /// The new value of [`ComputedVisibility::is_visible_in_view`]
+    pub is_visible_in_view: bool,
+}
+
+/// Update the [`ComputedVisibility`] component of entities based on the entity hierarchy and
+/// their [`Visible`] and [`Visibility`] components.
+///
+/// Hiding an entity hides its whole subtree, except for descendants forced on with
+/// [`Visibility::Visible`] and their own children. Showing it again restores each descendant to
+/// its own setting. Entities without a [`Visible`] component count as visible, so they don't
+/// interrupt the propagation.
+///
+/// Entities without a [`Parent`] are roots. An entity whose [`Parent`] has been despawned is a
+/// root as well. An entity that was re-parented this frame inherits from its new parent straight
+/// away, even while its new parent's [`Children`] doesn't list it yet and its old parent's still
+/// does.
+///
+/// [`ComputedVisibility`] is only written when its value flips, so `Changed<ComputedVisibility>`
+/// only matches entities that have just been shown, hidden, or moved in or out of view. A
+/// [`VisibilityChanged`] event is sent for each entity shown or hidden, and for each newly added
+/// [`ComputedVisibility`] once its first value is known, even if it matches the default.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn visibility_propagate_system(
+    mut reached: Local<HashSet<Entity>>,
+    mut root_query: Query<
+        (
+            Entity,
+            Option<&Children>,
+            Option<&Visible>,
+            Option<&Visibility>,
+            Option<&mut ComputedVisibility>,
+        ),
+        Without<Parent>,
+    >,
+    mut visibility_query: Query<(
+        &Parent,
+        Option<&Visible>,
+        Option<&Visibility>,
+        Option<&mut ComputedVisibility>,
+    )>,
+    children_query: Query<&Children>,
+    changed_parent_query: Query<(Entity, &Parent), Changed<Parent>>,
+    parent_query: Query<(Entity, &Parent)>,
+    ancestor_query: Query<(Option<&Visible>, Option<&Visibility>, Option<&Parent>)>,
+    mut visibility_changed_events: EventWriter<VisibilityChanged>,
+) {
+    reached.clear();
+    for (entity, children, visible, visibility, computed_visibility) in root_query.iter_mut() {
+        let is_visible = resolve(true, visible, visibility);
+        if let Some(mut computed_visibility) = computed_visibility {
+            update_computed_visibility(
+                entity,
+                &mut computed_visibility,
+                is_visible,
+                &mut visibility_changed_events,
+            );
+        }
+
+        if let Some(children) = children {
+            for child in children.iter() {
+                propagate_recursive(
+                    entity,
+                    is_visible,
+                    &mut visibility_query,
+                    &children_query,
+                    &mut visibility_changed_events,
+                    &mut reached,
+                    *child,
+                );
+            }
+        }
+    }
+
+    // entities re-parented this frame may not be listed in their new parent's `Children` yet, and
+    // orphaned entities have no parent to be listed in: neither can be reached from the roots
+    let orphans = parent_query
+        .iter()
+        .filter(|(_, parent)| ancestor_query.get(parent.0).is_err());
+    for (entity, parent) in changed_parent_query.iter().chain(orphans) {
+        if reached.contains(&entity) {
+            continue;
+        }
+        let parent_is_visible = inherited_visibility(&ancestor_query, parent.0);
+        propagate_recursive(
+            parent.0,
+            parent_is_visible,
+            &mut visibility_query,
+            &children_query,
+            &mut visibility_changed_events,
+            &mut reached,
+            entity,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn propagate_recursive(
+    expected_parent: Entity,
+    parent_is_visible: bool,
+    visibility_query: &mut Query<(
+        &Parent,
+        Option<&Visible>,
+        Option<&Visibility>,
+        Option<&mut ComputedVisibility>,
+    )>,
+    children_query: &Query<&Children>,
+    visibility_changed_events: &mut EventWriter<VisibilityChanged>,
+    reached: &mut HashSet<Entity>,
+    entity: Entity,
+) {
+    let is_visible = if let Ok((parent, visible, visibility, computed_visibility)) =
+        visibility_query.get_mut(entity)
+    {
+        // a re-parented entity stays listed in its old parent's `Children` until the end of
+        // the stage: it is handled from its new parent instead
+        if parent.0 != expected_parent || !reached.insert(entity) {
+            return;
+        }
+        let is_visible = resolve(parent_is_visible, visible, visibility);
+        if let Some(mut computed_visibility) = computed_visibility {
+            update_computed_visibility(
+                entity,
+                &mut computed_visibility,
+                is_visible,
+                visibility_changed_events,
+            );
+        }
+        is_visible
+    } else {
+        return;
+    };
+
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter() {
+            propagate_recursive(
+                entity,
+                is_visible,
+                visibility_query,
+                children_query,
+                visibility_changed_events,
+                reached,
+                *child,
+            );
+        }
+    }
+}
+
+/// Resolves the visibility of `entity` by walking up its ancestors. Missing entities count as
+/// visible, so a dangling [`Parent`] behaves like having no parent at all.
+#[allow(clippy::type_complexity)]
+fn inherited_visibility(
+    ancestor_query: &Query<(Option<&Visible>, Option<&Visibility>, Option<&Parent>)>,
+    entity: Entity,
+) -> bool {
+    if let Ok((visible, visibility, parent)) = ancestor_query.get(entity) {
+        let parent_is_visible = match parent {
+            Some(parent) => inherited_visibility(ancestor_query, parent.0),
+            None => true,
+        };
+        resolve(parent_is_visible, visible, visibility)
+    } else {
+        true
+    }
+}
+
+/// Returns whether an entity should be drawn: its [`ComputedVisibility::is_visible`] when it has
+/// one, so that entities hidden through one of their ancestors are skipped as well, or else its
+/// own [`Visible::is_visible`].
+pub fn is_visible(visible: &Visible, computed_visibility: Option<&ComputedVisibility>) -> bool {
+    computed_visibility.map_or(visible.is_visible, |computed_visibility| {
+        computed_visibility.is_visible
+    })
+}
+
+fn resolve(
+    parent_is_visible: bool,
+    visible: Option<&Visible>,
+    visibility: Option<&Visibility>,
+) -> bool {
+    visible.map(|visible| visible.is_visible).unwrap_or(true)
+        && visibility
+            .copied()
+            .unwrap_or_default()
+            .resolve(parent_is_visible)
+}
+
+/// Only write through `Mut` when the value differs, so `Changed<ComputedVisibility>` stays precise.
+fn update_computed_visibility(
+    entity: Entity,
+    computed_visibility: &mut Mut<ComputedVisibility>,
+    is_visible: bool,
+    visibility_changed_events: &mut EventWriter<VisibilityChanged>,
+) {
+    if computed_visibility.is_visible != is_visible {
+        computed_visibility.is_visible = is_visible;
+        visibility_changed_events.send(VisibilityChanged { entity, is_visible });
+    } else if computed_visibility.is_added() {
+        // spawning counts as a transition, whatever the default happened to be
+        visibility_changed_events.send(VisibilityChanged { entity, is_visible });
+    }
+}
+
+/// Update [`ComputedVisibility::is_visible_in_view`] and [`VisibilityRangeFade`] from the
+/// [`VisibleEntities`] of every camera, sending a [`ViewVisibilityChanged`] event for each newly
+/// added [`ComputedVisibility`] and each entity moved in or out of view.
+///
+/// This runs after `visible_entities_system`, which does the actual culling.
+pub fn view_visibility_system(
+    mut in_view: Local<HashMap<Entity, f32>>,
+    camera_query: Query<(Option<&GlobalTransform>, &VisibleEntities)>,
+    visibility_range_query: Query<(&VisibilityRange, &GlobalTransform)>,
+    mut computed_visibility_query: Query<(Entity, &mut ComputedVisibility)>,
+    mut visibility_range_fade_query: Query<(
+        Entity,
+        &mut VisibilityRangeFade,
+        Option<&VisibilityRange>,
+    )>,
+    mut view_visibility_changed_events: EventWriter<ViewVisibilityChanged>,
+) {
+    in_view.clear();
+    for (camera_transform, visible_entities) in camera_query.iter() {
+        for visible in visible_entities.iter() {
+            let fade = match (camera_transform, visibility_range_query.get(visible.entity)) {
+                (Some(camera_transform), Ok((visibility_range, transform))) => visibility_range
+                    .fade(camera_transform.translation.distance(transform.translation)),
+                _ => 1.0,
+            };
+            let max_fade = in_view.entry(visible.entity).or_insert(fade);
+            *max_fade = max_fade.max(fade);
+        }
+    }
+
+    for (entity, mut computed_visibility) in computed_visibility_query.iter_mut() {
+        let is_visible_in_view = in_view.contains_key(&entity);
+        let flipped = computed_visibility.is_visible_in_view != is_visible_in_view;
+        if flipped {
+            computed_visibility.is_visible_in_view = is_visible_in_view;
+        }
+        // spawning counts as a transition, like in `update_computed_visibility`
+        if flipped || computed_visibility.is_added() {
+            view_visibility_changed_events.send(ViewVisibilityChanged {
+                entity,
+                is_visible_in_view,
+            });
+        }
+    }
+
+    for (entity, mut visibility_range_fade, visibility_range) in
+        visibility_range_fade_query.iter_mut()
+    {
+        let fade = match visibility_range {
+            Some(_) => in_view.get(&entity).copied().unwrap_or(0.0),
+            None => 1.0,
+        };
+        if visibility_range_fade.0 != fade {
+            visibility_range_fade.0 = fade;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        event::Events,
+        query::Changed,
+        schedule::{ParallelSystemDescriptorCoercion, Schedule, Stage, SystemStage},
+        system::{IntoSystem, ResMut},
+        world::World,
+    };
+    use bevy_transform::hierarchy::{parent_update_system, BuildWorldChildren};
+
+    use super::*;
+
+    struct Hierarchy {
+        root: Entity,
+        child: Entity,
+        grandchildren: [Entity; 2],
+    }
+
+    fn spawn_hierarchy(world: &mut World) -> Hierarchy {
+        let mut grandchildren = Vec::new();
+        let mut child = None;
+        let root = world
+            .spawn()
+            .insert_bundle((Visible::default(), ComputedVisibility::default()))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_bundle((Visible::default(), ComputedVisibility::default()))
+                        .with_children(|parent| {
+                            for _ in 0..2 {
+                                grandchildren.push(
+                                    parent
+                                        .spawn_bundle((
+                                            Visible::default(),
+                                            ComputedVisibility::default(),
+                                        ))
+                                        .id(),
+                                );
+                            }
+                        })
+                        .id(),
+                );
+            })
+            .id();
+        Hierarchy {
+            root,
+            child: child.unwrap(),
+            grandchildren: [grandchildren[0], grandchildren[1]],
+        }
+    }
+
+    fn schedule() -> Schedule {
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system.system().label("parent_update"));
+        update_stage.add_system(visibility_propagate_system.system().after("parent_update"));
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+        schedule
+    }
+
+    fn world() -> World {
+        let mut world = World::default();
+        world.insert_resource(Events::<VisibilityChanged>::default());
+        world.insert_resource(Events::<ViewVisibilityChanged>::default());
+        world
+    }
+
+    fn set_visible(world: &mut World, entity: Entity, is_visible: bool) {
+        world.get_mut::<Visible>(entity).unwrap().is_visible = is_visible;
+    }
+
+    fn is_visible(world: &World, entity: Entity) -> bool {
+        world.get::<ComputedVisibility>(entity).unwrap().is_visible
+    }
+
+    #[test]
+    fn hidden_root_hides_subtree() {
+        let mut world = world();
+        let mut schedule = schedule();
+        let hierarchy = spawn_hierarchy(&mut world);
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.grandchildren[0]));
+
+        set_visible(&mut world, hierarchy.root, false);
+        schedule.run(&mut world);
+        assert!(!is_visible(&world, hierarchy.root));
+        assert!(!is_visible(&world, hierarchy.child));
+        assert!(!is_visible(&world, hierarchy.grandchildren[0]));
+        assert!(!is_visible(&world, hierarchy.grandchildren[1]));
+
+        // the children's own flags are left alone
+        assert!(world.get::<Visible>(hierarchy.child).unwrap().is_visible);
+
+        set_visible(&mut world, hierarchy.root, true);
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.root));
+        assert!(is_visible(&world, hierarchy.child));
+        assert!(is_visible(&world, hierarchy.grandchildren[0]));
+        assert!(is_visible(&world, hierarchy.grandchildren[1]));
+    }
+
+    #[test]
+    fn own_flag_wins_when_parent_is_shown_again() {
+        let mut world = world();
+        let mut schedule = schedule();
+        let hierarchy = spawn_hierarchy(&mut world);
+
+        set_visible(&mut world, hierarchy.grandchildren[0], false);
+        set_visible(&mut world, hierarchy.child, false);
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.root));
+        assert!(!is_visible(&world, hierarchy.child));
+        assert!(!is_visible(&world, hierarchy.grandchildren[0]));
+        assert!(!is_visible(&world, hierarchy.grandchildren[1]));
+
+        set_visible(&mut world, hierarchy.child, true);
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.child));
+        assert!(!is_visible(&world, hierarchy.grandchildren[0]));
+        assert!(is_visible(&world, hierarchy.grandchildren[1]));
+    }
+
+    #[test]
+    fn entities_without_visible_pass_visibility_through() {
+        let mut world = world();
+        let mut schedule = schedule();
+
+        let mut child = None;
+        let root = world
+            .spawn()
+            .insert(Visible {
+                is_visible: false,
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn().with_children(|parent| {
+                    child = Some(parent.spawn_bundle((ComputedVisibility::default(),)).id());
+                });
+            })
+            .id();
+        schedule.run(&mut world);
+        assert!(!is_visible(&world, child.unwrap()));
+
+        set_visible(&mut world, root, true);
+        schedule.run(&mut world);
+        assert!(is_visible(&world, child.unwrap()));
+    }
+
+    #[test]
+    fn visibility_states_under_parent() {
+        let cases = [
+            (true, Visibility::Inherited, true),
+            (false, Visibility::Inherited, false),
+            (true, Visibility::Hidden, false),
+            (false, Visibility::Hidden, false),
+            (true, Visibility::Visible, true),
+            (false, Visibility::Visible, true),
+        ];
+        for &(parent_is_visible, visibility, expected) in cases.iter() {
+            let mut world = world();
+            let mut schedule = schedule();
+            let hierarchy = spawn_hierarchy(&mut world);
+            set_visible(&mut world, hierarchy.root, parent_is_visible);
+            world.entity_mut(hierarchy.child).insert(visibility);
+            schedule.run(&mut world);
+
+            let case = (parent_is_visible, visibility);
+            assert_eq!(is_visible(&world, hierarchy.child), expected, "{:?}", case);
+            // grandchildren inherit whatever the child resolved to
+            assert_eq!(
+                is_visible(&world, hierarchy.grandchildren[0]),
+                expected,
+                "{:?}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn hidden_visible_flag_wins_over_forced_visibility() {
+        let mut world = world();
+        let mut schedule = schedule();
+        let hierarchy = spawn_hierarchy(&mut world);
+        world
+            .entity_mut(hierarchy.child)
+            .insert(Visibility::Visible);
+        set_visible(&mut world, hierarchy.child, false);
+        schedule.run(&mut world);
+        assert!(!is_visible(&world, hierarchy.child));
+    }
+
+    #[test]
+    fn visibility_from_bool() {
+        assert_eq!(Visibility::from(true), Visibility::Inherited);
+        assert_eq!(Visibility::from(false), Visibility::Hidden);
+        assert_eq!(Visibility::default(), Visibility::Inherited);
+    }
+
+    #[derive(Default)]
+    struct ChangedEntities(Vec<Entity>);
+
+    fn changed_entities_system(
+        mut changed: ResMut<ChangedEntities>,
+        query: Query<Entity, Changed<ComputedVisibility>>,
+    ) {
+        changed.0 = query.iter().collect();
+    }
+
+    fn visibility_changed_events(world: &mut World) -> Vec<VisibilityChanged> {
+        world
+            .get_resource_mut::<Events<VisibilityChanged>>()
+            .unwrap()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn changes_are_only_reported_on_transitions() {
+        let mut world = world();
+        world.insert_resource(ChangedEntities::default());
+        let mut schedule = schedule();
+        schedule.add_stage(
+            "changed",
+            SystemStage::single(changed_entities_system.system()),
+        );
+        let hierarchy = spawn_hierarchy(&mut world);
+
+        // the first computation is reported, even though it matches the default
+        schedule.run(&mut world);
+        let events = visibility_changed_events(&mut world);
+        assert_eq!(events.len(), 4);
+        assert!(events.iter().all(|event| event.is_visible));
+        assert!(events.contains(&VisibilityChanged {
+            entity: hierarchy.root,
+            is_visible: true,
+        }));
+
+        // steady state: visible -> visible
+        schedule.run(&mut world);
+        assert!(world
+            .get_resource::<ChangedEntities>()
+            .unwrap()
+            .0
+            .is_empty());
+        assert!(visibility_changed_events(&mut world).is_empty());
+
+        set_visible(&mut world, hierarchy.child, false);
+        schedule.run(&mut world);
+        let mut changed = world.get_resource::<ChangedEntities>().unwrap().0.clone();
+        changed.sort();
+        let mut expected = vec![
+            hierarchy.child,
+            hierarchy.grandchildren[0],
+            hierarchy.grandchildren[1],
+        ];
+        expected.sort();
+        assert_eq!(changed, expected);
+        let events = visibility_changed_events(&mut world);
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| !event.is_visible));
+        assert!(events.contains(&VisibilityChanged {
+            entity: hierarchy.child,
+            is_visible: false,
+        }));
+
+        // steady state: hidden -> hidden
+        schedule.run(&mut world);
+        assert!(world
+            .get_resource::<ChangedEntities>()
+            .unwrap()
+            .0
+            .is_empty());
+        assert!(visibility_changed_events(&mut world).is_empty());
+
+        set_visible(&mut world, hierarchy.child, true);
+        schedule.run(&mut world);
+        assert_eq!(world.get_resource::<ChangedEntities>().unwrap().0.len(), 3);
+        let events = visibility_changed_events(&mut world);
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| event.is_visible));
+    }
+
+    #[test]
+    fn spawning_is_reported_once() {
+        let mut world = world();
+        let mut schedule = schedule();
+        let hierarchy = spawn_hierarchy(&mut world);
+        schedule.run(&mut world);
+        visibility_changed_events(&mut world);
+
+        // entities spawned later report their first value, visible or not
+        let shown = world
+            .spawn()
+            .insert_bundle((Visible::default(), ComputedVisibility::default()))
+            .id();
+        let hidden = world
+            .spawn()
+            .insert_bundle((
+                Visible {
+                    is_visible: false,
+                    ..Default::default()
+                },
+                ComputedVisibility::default(),
+            ))
+            .id();
+        world.entity_mut(hierarchy.child).push_children(&[hidden]);
+        schedule.run(&mut world);
+        let mut events = visibility_changed_events(&mut world);
+        events.sort_by_key(|event| event.entity);
+        let mut expected = vec![
+            VisibilityChanged {
+                entity: shown,
+                is_visible: true,
+            },
+            VisibilityChanged {
+                entity: hidden,
+                is_visible: false,
+            },
+        ];
+        expected.sort_by_key(|event| event.entity);
+        assert_eq!(events, expected);
+
+        schedule.run(&mut world);
+        assert!(visibility_changed_events(&mut world).is_empty());
+    }
+
+    #[test]
+    fn culling_is_reported_separately() {
+        use crate::camera::{visible_entities_system, Camera, MaxVisibleDistance};
+        use bevy_math::Vec3;
+        use bevy_transform::components::GlobalTransform;
+
+        let mut world = world();
+        let mut schedule = schedule();
+        schedule.add_stage(
+            "view",
+            SystemStage::single(visible_entities_system.system()),
+        );
+        schedule.add_stage(
+            "view_visibility",
+            SystemStage::single(view_visibility_system.system()),
+        );
+        world.spawn().insert_bundle((
+            Camera::default(),
+            GlobalTransform::identity(),
+            VisibleEntities::default(),
+        ));
+
+        let mut spawn = |is_visible: bool, distance: f32| {
+            world
+                .spawn()
+                .insert_bundle((
+                    Visible {
+                        is_visible,
+                        ..Default::default()
+                    },
+                    ComputedVisibility::default(),
+                    GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -distance)),
+                    MaxVisibleDistance(10.0),
+                ))
+                .id()
+        };
+        let shown = spawn(true, 1.0);
+        let hidden = spawn(false, 1.0);
+        let culled = spawn(true, 100.0);
+        schedule.run(&mut world);
+
+        let computed = |entity| {
+            let computed_visibility = world.get::<ComputedVisibility>(entity).unwrap();
+            (
+                computed_visibility.is_visible,
+                computed_visibility.is_visible_in_view,
+            )
+        };
+        assert_eq!(computed(shown), (true, true));
+        assert_eq!(computed(hidden), (false, false));
+        assert_eq!(computed(culled), (true, false));
+    }
+
+    #[test]
+    fn view_transitions_are_reported() {
+        use crate::camera::{visible_entities_system, Camera, MaxVisibleDistance};
+        use bevy_math::Vec3;
+
+        fn view_visibility_changed_events(world: &mut World) -> Vec<ViewVisibilityChanged> {
+            world
+                .get_resource_mut::<Events<ViewVisibilityChanged>>()
+                .unwrap()
+                .drain()
+                .collect()
+        }
+
+        let mut world = world();
+        let mut schedule = schedule();
+        schedule.add_stage(
+            "view",
+            SystemStage::single(visible_entities_system.system()),
+        );
+        schedule.add_stage(
+            "view_visibility",
+            SystemStage::single(view_visibility_system.system()),
+        );
+        world.spawn().insert_bundle((
+            Camera::default(),
+            GlobalTransform::identity(),
+            VisibleEntities::default(),
+        ));
+        let entity = world
+            .spawn()
+            .insert_bundle((
+                Visible::default(),
+                ComputedVisibility::default(),
+                GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -100.0)),
+                MaxVisibleDistance(10.0),
+            ))
+            .id();
+
+        // spawning out of view is reported, even though it matches the default
+        schedule.run(&mut world);
+        assert_eq!(
+            view_visibility_changed_events(&mut world),
+            vec![ViewVisibilityChanged {
+                entity,
+                is_visible_in_view: false,
+            }]
+        );
+        schedule.run(&mut world);
+        assert!(view_visibility_changed_events(&mut world).is_empty());
+
+        world
+            .get_mut::<GlobalTransform>(entity)
+            .unwrap()
+            .translation = Vec3::new(0.0, 0.0, -5.0);
+        schedule.run(&mut world);
+        assert_eq!(
+            view_visibility_changed_events(&mut world),
+            vec![ViewVisibilityChanged {
+                entity,
+                is_visible_in_view: true,
+            }]
+        );
+        schedule.run(&mut world);
+        assert!(view_visibility_changed_events(&mut world).is_empty());
+
+        // moving past the maximum distance culls it again
+        world
+            .get_mut::<GlobalTransform>(entity)
+            .unwrap()
+            .translation = Vec3::new(0.0, 0.0, -20.0);
+        schedule.run(&mut world);
+        assert_eq!(
+            view_visibility_changed_events(&mut world),
+            vec![ViewVisibilityChanged {
+                entity,
+                is_visible_in_view: false,
+            }]
+        );
+        // which isn't a change to the hierarchy visibility
+        assert!(is_visible(&world, entity));
+    }
+
+    #[test]
+    fn visibility_range_fade_is_reported() {
+        use crate::camera::{visible_entities_system, Camera};
+        use bevy_math::Vec3;
+
+        let mut world = world();
+        let mut schedule = schedule();
+        schedule.add_stage(
+            "view",
+            SystemStage::single(visible_entities_system.system()),
+        );
+        schedule.add_stage(
+            "view_visibility",
+            SystemStage::single(view_visibility_system.system()),
+        );
+        let mut spawn_camera = |z: f32| {
+            world
+                .spawn()
+                .insert_bundle((
+                    Camera::default(),
+                    GlobalTransform::from_translation(Vec3::new(0.0, 0.0, z)),
+                    VisibleEntities::default(),
+                ))
+                .id()
+        };
+        spawn_camera(0.0);
+        let second_camera = spawn_camera(-100.0);
+
+        let mut spawn = |distance: f32, visibility_range: Option<VisibilityRange>| {
+            let mut entity = world.spawn();
+            entity.insert_bundle((
+                Visible::default(),
+                ComputedVisibility::default(),
+                VisibilityRangeFade::default(),
+                GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -distance)),
+            ));
+            if let Some(visibility_range) = visibility_range {
+                entity.insert(visibility_range);
+            }
+            entity.id()
+        };
+        let range = VisibilityRange {
+            start: 0.0,
+            start_margin: 0.0,
+            end: 20.0,
+            end_margin: 4.0,
+        };
+        let fading = spawn(19.0, Some(range));
+        let inside = spawn(10.0, Some(range));
+        let culled = spawn(50.0, Some(range));
+        let unranged = spawn(50.0, None);
+
+        let computed = |world: &World, entity| {
+            (
+                world
+                    .get::<ComputedVisibility>(entity)
+                    .unwrap()
+                    .is_visible_in_view,
+                world.get::<VisibilityRangeFade>(entity).unwrap().0,
+            )
+        };
+        schedule.run(&mut world);
+        assert_eq!(computed(&world, fading), (true, 0.25));
+        assert_eq!(computed(&world, inside), (true, 1.0));
+        assert_eq!(computed(&world, culled), (false, 0.0));
+        assert_eq!(computed(&world, unranged), (true, 1.0));
+
+        // an entity seen by several cameras takes the largest fade
+        world
+            .get_mut::<GlobalTransform>(second_camera)
+            .unwrap()
+            .translation = Vec3::new(0.0, 0.0, -37.0);
+        schedule.run(&mut world);
+        assert_eq!(computed(&world, fading), (true, 0.5));
+        assert_eq!(computed(&world, culled), (true, 1.0));
+    }
+
+    #[test]
+    fn reparenting_takes_effect_in_the_same_frame() {
+        let mut world = world();
+        let mut schedule = schedule();
+        let hierarchy = spawn_hierarchy(&mut world);
+        let other_root = world
+            .spawn()
+            .insert_bundle((
+                Visible {
+                    is_visible: false,
+                    ..Default::default()
+                },
+                ComputedVisibility::default(),
+            ))
+            .id();
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.child));
+
+        // the new parent has no `Children` yet: they're only inserted at the end of the stage
+        world.entity_mut(hierarchy.child).insert(Parent(other_root));
+        schedule.run(&mut world);
+        assert!(!is_visible(&world, hierarchy.child));
+        assert!(!is_visible(&world, hierarchy.grandchildren[0]));
+
+        world
+            .entity_mut(hierarchy.child)
+            .insert(Parent(hierarchy.root));
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.child));
+        assert!(is_visible(&world, hierarchy.grandchildren[1]));
+    }
+
+    #[test]
+    fn reparented_entity_is_only_updated_once() {
+        let mut world = world();
+        world.insert_resource(ChangedEntities::default());
+        let mut schedule = schedule();
+        schedule.add_stage(
+            "changed",
+            SystemStage::single(changed_entities_system.system()),
+        );
+        let hierarchy = spawn_hierarchy(&mut world);
+        let other_root = world
+            .spawn()
+            .insert_bundle((Visible::default(), ComputedVisibility::default()))
+            .id();
+        schedule.run(&mut world);
+        visibility_changed_events(&mut world);
+
+        // hide the old parent while moving the child to a visible one: `parent_update_system` only
+        // removes the child from the old parent's `Children` at the end of the stage, so it is
+        // still listed there during propagation, but must not be hidden through it
+        set_visible(&mut world, hierarchy.root, false);
+        world.entity_mut(hierarchy.child).insert(Parent(other_root));
+        schedule.run(&mut world);
+
+        assert!(!is_visible(&world, hierarchy.root));
+        assert!(is_visible(&world, hierarchy.child));
+        assert!(is_visible(&world, hierarchy.grandchildren[0]));
+        assert_eq!(
+            visibility_changed_events(&mut world),
+            vec![VisibilityChanged {
+                entity: hierarchy.root,
+                is_visible: false,
+            }]
+        );
+        assert_eq!(
+            world.get_resource::<ChangedEntities>().unwrap().0,
+            vec![hierarchy.root]
+        );
+    }
+
+    #[test]
+    fn entities_without_a_parent_are_roots() {
+        let mut world = world();
+        let mut schedule = schedule();
+        let hierarchy = spawn_hierarchy(&mut world);
+        set_visible(&mut world, hierarchy.root, false);
+        schedule.run(&mut world);
+        assert!(!is_visible(&world, hierarchy.child));
+
+        // removing `Parent` turns the child into a root
+        world.entity_mut(hierarchy.child).remove::<Parent>();
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.child));
+        assert!(is_visible(&world, hierarchy.grandchildren[0]));
+
+        // so does despawning the parent without its children
+        let hierarchy = spawn_hierarchy(&mut world);
+        set_visible(&mut world, hierarchy.root, false);
+        schedule.run(&mut world);
+        assert!(!is_visible(&world, hierarchy.grandchildren[1]));
+        world.despawn(hierarchy.root);
+        schedule.run(&mut world);
+        assert!(is_visible(&world, hierarchy.child));
+        assert!(is_visible(&world, hierarchy.grandchildren[1]));
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     pipeline::{PipelineDescriptor, PipelineSpecialization, RenderPipeline},
     prelude::*,
     shader::Shader,
+    visibility,
 };
 use bevy_app::prelude::*;
 use bevy_asset::{Assets, Handle, HandleUntyped};
@@ -62,17 +63,33 @@ pub fn draw_wireframes_system(
     meshes: Res<Assets<Mesh>>,
     wireframe_config: Res<WireframeConfig>,
     mut query: QuerySet<(
-        Query<(&mut Draw, &mut RenderPipelines, &Handle<Mesh>, &Visible)>,
-        Query<(&mut Draw, &mut RenderPipelines, &Handle<Mesh>, &Visible), With<Wireframe>>,
+        Query<(
+            &mut Draw,
+            &mut RenderPipelines,
+            &Handle<Mesh>,
+            &Visible,
+            Option<&ComputedVisibility>,
+        )>,
+        Query<
+            (
+                &mut Draw,
+                &mut RenderPipelines,
+                &Handle<Mesh>,
+                &Visible,
+                Option<&ComputedVisibility>,
+            ),
+            With<Wireframe>,
+        >,
     )>,
 ) {
-    let iterator = |(mut draw, mut render_pipelines, mesh_handle, visible): (
+    let iterator = |(mut draw, mut render_pipelines, mesh_handle, visible, computed): (
         Mut<Draw>,
         Mut<RenderPipelines>,
         &Handle<Mesh>,
         &Visible,
+        Option<&ComputedVisibility>,
     )| {
-        if !visible.is_visible {
+        if !visibility::is_visible(visible, computed) {
             return;
         }
 
@@ -7,7 +7,7 @@ use bevy_ecs::bundle::Bundle;
 use bevy_render::{
     mesh::Mesh,
     pipeline::{RenderPipeline, RenderPipelines},
-    prelude::{Draw, Visible},
+    prelude::{ComputedVisibility, Draw, Visible},
     render_graph::base::MainPass,
 };
 use bevy_transform::prelude::{GlobalTransform, Transform};
@@ -20,6 +20,7 @@ pub struct SpriteBundle {
     pub main_pass: MainPass,
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
@@ -40,6 +41,7 @@ impl Default for SpriteBundle {
             draw: Default::default(),
             sprite: Default::default(),
             material: Default::default(),
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -57,6 +59,7 @@ pub struct SpriteSheetBundle {
     /// Data pertaining to how the sprite is drawn on the screen
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub render_pipelines: RenderPipelines,
     pub main_pass: MainPass,
     pub mesh: Handle<Mesh>, // TODO: maybe abstract this out
@@ -79,6 +82,7 @@ impl Default for SpriteSheetBundle {
             draw: Default::default(),
             sprite: Default::default(),
             texture_atlas: Default::default(),
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -14,7 +14,7 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         entity::{SpriteBundle, SpriteSheetBundle},
-        ColorMaterial, Sprite, SpriteResizeMode, TextureAtlas, TextureAtlasSprite,
+        ColorMaterial, IRect, Sprite, SpriteResizeMode, TextureAtlas, TextureAtlasSprite, URect,
     };
 }
 
@@ -1,10 +1,10 @@
 use bevy_core::{Pod, Zeroable};
-use bevy_math::Vec2;
+use bevy_math::{IVec2, UVec2, Vec2};
 
 /// A rectangle defined by two points. There is no defined origin, so 0,0 could be anywhere
 /// (top-left, bottom-left, etc)
 #[repr(C)]
-#[derive(Default, Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct Rect {
     /// The beginning point of the rect
     pub min: Vec2,
@@ -13,11 +13,542 @@ pub struct Rect {
 }
 
 impl Rect {
+    /// The extent of the rect along the x axis, `max.x - min.x`
     pub fn width(&self) -> f32 {
         self.max.x - self.min.x
     }
 
+    /// The extent of the rect along the y axis, `max.y - min.y`
     pub fn height(&self) -> f32 {
         self.max.y - self.min.y
     }
+
+    /// Creates a rect from two opposite corners, given in any order
+    pub fn from_corners(p0: Vec2, p1: Vec2) -> Rect {
+        Rect {
+            min: p0.min(p1),
+            max: p0.max(p1),
+        }
+    }
+
+    /// Creates a rect of the given `size`, centered on `center`
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Rect {
+        Rect::from_center_half_size(center, size / 2.0)
+    }
+
+    /// Creates a rect centered on `center`, extending `half_size` from it in each direction
+    pub fn from_center_half_size(center: Vec2, half_size: Vec2) -> Rect {
+        let half_size = half_size.abs();
+        Rect {
+            min: center - half_size,
+            max: center + half_size,
+        }
+    }
+
+    /// The width and height of the rect, `max - min`
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// The point halfway between `min` and `max`
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Half of the rect's size, i.e. the distance from its center to its edges
+    pub fn half_size(&self) -> Vec2 {
+        self.size() / 2.0
+    }
+
+    /// Half of the rect's size, named after [`Aabb::half_extents`](bevy_math::Aabb::half_extents)
+    /// for code working with both types. This is the same as [`Rect::half_size`].
+    pub fn half_extents(&self) -> Vec2 {
+        self.half_size()
+    }
+
+    /// Returns the same rect with its corners swapped as needed, so that `min` is smaller than or
+    /// equal to `max` on both axes. Useful for rects built by dragging in any direction.
+    pub fn normalize(&self) -> Rect {
+        let (min, max) = self.ordered_corners();
+        Rect { min, max }
+    }
+
+    /// Returns true if the rect has no area
+    pub fn is_empty(&self) -> bool {
+        self.min.x == self.max.x || self.min.y == self.max.y
+    }
+
+    /// Returns true if `point` is inside the rect or on its edges. Rects with swapped corners are
+    /// handled as if their corners were ordered.
+    pub fn contains(&self, point: Vec2) -> bool {
+        let (min, max) = self.ordered_corners();
+        min.x <= point.x && point.x <= max.x && min.y <= point.y && point.y <= max.y
+    }
+
+    /// Returns true if `other` lies entirely inside this rect, edges included
+    pub fn contains_rect(&self, other: Rect) -> bool {
+        let (other_min, other_max) = other.ordered_corners();
+        self.contains(other_min) && self.contains(other_max)
+    }
+
+    /// Returns true if the two rects overlap. Rects that only share an edge or a corner are
+    /// considered intersecting.
+    pub fn intersects(&self, other: Rect) -> bool {
+        let (min, max) = self.ordered_corners();
+        let (other_min, other_max) = other.ordered_corners();
+        min.x <= other_max.x && other_min.x <= max.x && min.y <= other_max.y && other_min.y <= max.y
+    }
+
+    /// Returns the area covered by both rects. If they don't overlap, the result is empty.
+    pub fn intersect(&self, other: Rect) -> Rect {
+        let (min, max) = self.ordered_corners();
+        let (other_min, other_max) = other.ordered_corners();
+        let min = min.max(other_min);
+        Rect {
+            min,
+            max: max.min(other_max).max(min),
+        }
+    }
+
+    /// Returns the smallest rect containing both rects
+    pub fn union(&self, other: Rect) -> Rect {
+        let (min, max) = self.ordered_corners();
+        let (other_min, other_max) = other.ordered_corners();
+        Rect {
+            min: min.min(other_min),
+            max: max.max(other_max),
+        }
+    }
+
+    /// Grows the rect by `amount` on every side, or shrinks it if `amount` is negative.
+    ///
+    /// Shrinking an axis past its size collapses it to zero size around the rect's center,
+    /// rather than producing an inverted rect.
+    pub fn inflate(&self, amount: f32) -> Rect {
+        self.inset(-amount, -amount, -amount, -amount)
+    }
+
+    /// Shrinks the rect by the given margin on each side, or grows it for negative margins.
+    /// `top` is applied to the smallest y coordinate, matching texture coordinates where y grows
+    /// downwards.
+    ///
+    /// Shrinking an axis past its size collapses it to zero size around the rect's center,
+    /// rather than producing an inverted rect.
+    pub fn inset(&self, left: f32, top: f32, right: f32, bottom: f32) -> Rect {
+        let (min, max) = self.ordered_corners();
+        let center = (min + max) / 2.0;
+        let mut min = min + Vec2::new(left, top);
+        let mut max = max - Vec2::new(right, bottom);
+        if min.x > max.x {
+            min.x = center.x;
+            max.x = center.x;
+        }
+        if min.y > max.y {
+            min.y = center.y;
+            max.y = center.y;
+        }
+        Rect { min, max }
+    }
+
+    /// Interpolates each corner of the rect independently towards the matching corner of
+    /// `other`. `t` is usually between `0.0` and `1.0`, which return exactly `self` and `other`.
+    pub fn lerp(&self, other: Rect, t: f32) -> Rect {
+        // unlike `a + (b - a) * t`, this lands exactly on `b` when `t` is 1
+        Rect {
+            min: self.min * (1.0 - t) + other.min * t,
+            max: self.max * (1.0 - t) + other.max * t,
+        }
+    }
+
+    /// Scales the rect by `factor` on each axis, keeping `pivot` in place. A factor above one
+    /// grows the rect away from the pivot, while a factor below one shrinks it towards the
+    /// pivot.
+    ///
+    /// A negative factor mirrors the rect around the pivot, which swaps its corners on that
+    /// axis: see [`Rect::normalize`].
+    pub fn scaled_about(&self, pivot: Vec2, factor: Vec2) -> Rect {
+        Rect {
+            min: pivot + (self.min - pivot) * factor,
+            max: pivot + (self.max - pivot) * factor,
+        }
+    }
+
+    /// Converts the rect to a [`URect`], rounding outwards so that the result covers every pixel
+    /// the rect touches. Coordinates below zero are clamped to zero.
+    pub fn as_urect(&self) -> URect {
+        let (min, max) = self.ordered_corners();
+        URect {
+            min: min.floor().as_u32(),
+            max: max.ceil().as_u32(),
+        }
+    }
+
+    /// Converts the rect to an [`IRect`], rounding outwards so that the result covers every pixel
+    /// the rect touches.
+    pub fn as_irect(&self) -> IRect {
+        let (min, max) = self.ordered_corners();
+        IRect {
+            min: min.floor().as_i32(),
+            max: max.ceil().as_i32(),
+        }
+    }
+
+    /// The corners of the rect, ordered so that the first one is the smallest on each axis
+    fn ordered_corners(&self) -> (Vec2, Vec2) {
+        (self.min.min(self.max), self.min.max(self.max))
+    }
+}
+
+/// A rectangle defined by two unsigned integer points, such as a region of texture pixels.
+/// Methods expect `min` to be smaller than or equal to `max` on both axes.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct URect {
+    /// The beginning point of the rect
+    pub min: UVec2,
+    /// The ending point of the rect
+    pub max: UVec2,
+}
+
+/// A rectangle defined by two signed integer points. Methods expect `min` to be smaller than or
+/// equal to `max` on both axes.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IRect {
+    /// The beginning point of the rect
+    pub min: IVec2,
+    /// The ending point of the rect
+    pub max: IVec2,
+}
+
+macro_rules! impl_integer_rect {
+    ($rect:ident, $vec:ident, $t:ty) => {
+        impl $rect {
+            /// Creates a rect from two opposite corners, given in any order
+            pub fn from_corners(p0: $vec, p1: $vec) -> Self {
+                $rect {
+                    min: p0.min(p1),
+                    max: p0.max(p1),
+                }
+            }
+
+            /// Creates a rect of the given `size`, centered on `center`. When `size` is odd,
+            /// the extra unit is placed after the center.
+            ///
+            /// Coordinates saturate instead of overflowing, so a [`URect`] that would extend
+            /// below zero is clipped to zero.
+            pub fn from_center_size(center: $vec, size: $vec) -> Self {
+                let before = size / 2;
+                let after = size - before;
+                $rect {
+                    min: $vec::new(
+                        center.x.saturating_sub(before.x),
+                        center.y.saturating_sub(before.y),
+                    ),
+                    max: $vec::new(
+                        center.x.saturating_add(after.x),
+                        center.y.saturating_add(after.y),
+                    ),
+                }
+            }
+
+            /// The extent of the rect along the x axis, `max.x - min.x`
+            pub fn width(&self) -> $t {
+                self.max.x - self.min.x
+            }
+
+            /// The extent of the rect along the y axis, `max.y - min.y`
+            pub fn height(&self) -> $t {
+                self.max.y - self.min.y
+            }
+
+            /// The width and height of the rect, `max - min`
+            pub fn size(&self) -> $vec {
+                self.max - self.min
+            }
+
+            /// The center of the rect. When the size is odd, this is rounded towards `min`.
+            pub fn center(&self) -> $vec {
+                self.min + self.size() / 2
+            }
+
+            /// Returns true if the rect has no area
+            pub fn is_empty(&self) -> bool {
+                self.min.x == self.max.x || self.min.y == self.max.y
+            }
+
+            /// Returns true if `point` is inside the rect or on its edges
+            pub fn contains(&self, point: $vec) -> bool {
+                self.min.x <= point.x
+                    && point.x <= self.max.x
+                    && self.min.y <= point.y
+                    && point.y <= self.max.y
+            }
+
+            /// Returns the area covered by both rects. If they don't overlap, the result is
+            /// empty.
+            pub fn intersect(&self, other: Self) -> Self {
+                let min = self.min.max(other.min);
+                $rect {
+                    min,
+                    max: self.max.min(other.max).max(min),
+                }
+            }
+
+            /// Returns the smallest rect containing both rects
+            pub fn union(&self, other: Self) -> Self {
+                $rect {
+                    min: self.min.min(other.min),
+                    max: self.max.max(other.max),
+                }
+            }
+
+            /// Converts the rect to a float [`Rect`]. This is exact as long as coordinates stay
+            /// within +/-2^24.
+            pub fn as_rect(&self) -> Rect {
+                Rect {
+                    min: self.min.as_f32(),
+                    max: self.max.as_f32(),
+                }
+            }
+        }
+    };
+}
+
+impl_integer_rect!(URect, UVec2, u32);
+impl_integer_rect!(IRect, IVec2, i32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Rect {
+        Rect {
+            min: Vec2::new(min_x, min_y),
+            max: Vec2::new(max_x, max_y),
+        }
+    }
+
+    #[test]
+    fn rect_contains() {
+        let r = rect(0.0, 0.0, 10.0, 5.0);
+        assert!(r.contains(Vec2::new(5.0, 2.5)));
+        assert!(r.contains(Vec2::new(0.0, 0.0)));
+        assert!(r.contains(Vec2::new(10.0, 5.0)));
+        assert!(r.contains(Vec2::new(10.0, 0.0)));
+        assert!(!r.contains(Vec2::new(10.001, 2.5)));
+        assert!(!r.contains(Vec2::new(5.0, -0.001)));
+
+        let swapped = rect(10.0, 5.0, 0.0, 0.0);
+        assert!(swapped.contains(Vec2::new(5.0, 2.5)));
+        assert!(swapped.contains(Vec2::new(0.0, 5.0)));
+        assert!(!swapped.contains(Vec2::new(11.0, 2.5)));
+    }
+
+    #[test]
+    fn rect_contains_rect() {
+        let r = rect(0.0, 0.0, 10.0, 10.0);
+        assert!(r.contains_rect(rect(2.0, 2.0, 8.0, 8.0)));
+        assert!(r.contains_rect(r));
+        assert!(r.contains_rect(rect(8.0, 8.0, 2.0, 2.0)));
+        assert!(!r.contains_rect(rect(5.0, 5.0, 15.0, 15.0)));
+        assert!(!r.contains_rect(rect(20.0, 20.0, 30.0, 30.0)));
+    }
+
+    #[test]
+    fn rect_intersects() {
+        let r = rect(0.0, 0.0, 10.0, 10.0);
+        assert!(r.intersects(rect(2.0, 2.0, 8.0, 8.0)));
+        assert!(r.intersects(rect(5.0, 5.0, 15.0, 15.0)));
+        assert!(r.intersects(rect(10.0, 10.0, 15.0, 15.0)));
+        assert!(r.intersects(rect(15.0, 15.0, 5.0, 5.0)));
+        assert!(!r.intersects(rect(10.5, 0.0, 15.0, 10.0)));
+        assert!(!r.intersects(rect(0.0, -5.0, 10.0, -0.5)));
+    }
+
+    #[test]
+    fn rect_inflate() {
+        let r = rect(0.0, 0.0, 10.0, 4.0);
+        assert_eq!(r.inflate(1.0), rect(-1.0, -1.0, 11.0, 5.0));
+        assert_eq!(r.inflate(-1.0), rect(1.0, 1.0, 9.0, 3.0));
+
+        // over-shrinking collapses each axis onto the center
+        assert_eq!(r.inflate(-3.0), rect(3.0, 2.0, 7.0, 2.0));
+        assert_eq!(r.inflate(-20.0), rect(5.0, 2.0, 5.0, 2.0));
+    }
+
+    #[test]
+    fn rect_inset() {
+        let r = rect(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(r.inset(1.0, 2.0, 3.0, 4.0), rect(1.0, 2.0, 7.0, 6.0));
+        assert_eq!(r.inset(-1.0, 0.0, 0.0, 0.0), rect(-1.0, 0.0, 10.0, 10.0));
+
+        // margins that don't fit collapse the axis onto the original center
+        assert_eq!(r.inset(8.0, 0.0, 8.0, 0.0), rect(5.0, 0.0, 5.0, 10.0));
+    }
+
+    #[test]
+    fn rect_from_corners_and_center() {
+        let r = Rect::from_corners(Vec2::new(4.0, 0.0), Vec2::new(0.0, 2.0));
+        assert_eq!(r, rect(0.0, 0.0, 4.0, 2.0));
+        assert_eq!(r.size(), Vec2::new(4.0, 2.0));
+        assert_eq!(r.center(), Vec2::new(2.0, 1.0));
+        assert_eq!(
+            Rect::from_center_size(Vec2::new(2.0, 1.0), Vec2::new(4.0, 2.0)),
+            r
+        );
+    }
+
+    #[test]
+    fn rect_intersect_union() {
+        let a = rect(0.0, 0.0, 4.0, 4.0);
+        let b = rect(2.0, 2.0, 6.0, 6.0);
+        assert_eq!(a.intersect(b), rect(2.0, 2.0, 4.0, 4.0));
+        assert_eq!(a.union(b), rect(0.0, 0.0, 6.0, 6.0));
+        assert!(a.intersect(rect(5.0, 5.0, 6.0, 6.0)).is_empty());
+    }
+
+    #[test]
+    fn integer_rects() {
+        let r = URect::from_center_size(UVec2::new(5, 5), UVec2::new(4, 3));
+        assert_eq!(r.min, UVec2::new(3, 4));
+        assert_eq!(r.max, UVec2::new(7, 7));
+        assert_eq!(r.width(), 4);
+        assert_eq!(r.height(), 3);
+        assert_eq!(r.center(), UVec2::new(5, 5));
+        assert!(r.contains(UVec2::new(7, 7)));
+        assert!(!r.contains(UVec2::new(8, 7)));
+
+        let a = IRect::from_corners(IVec2::new(2, 2), IVec2::new(-2, -2));
+        let b = IRect::from_corners(IVec2::new(0, 0), IVec2::new(4, 4));
+        assert_eq!(
+            a.intersect(b),
+            IRect::from_corners(IVec2::ZERO, IVec2::new(2, 2))
+        );
+        assert_eq!(
+            a.union(b),
+            IRect::from_corners(IVec2::new(-2, -2), IVec2::new(4, 4))
+        );
+        assert!(a
+            .intersect(IRect::from_corners(IVec2::new(3, 3), IVec2::new(4, 4)))
+            .is_empty());
+    }
+
+    #[test]
+    fn integer_rect_from_center_size_saturates() {
+        // the part of the rect below zero is clipped instead of underflowing
+        let r = URect::from_center_size(UVec2::new(1, 5), UVec2::new(4, 4));
+        assert_eq!(r, URect::from_corners(UVec2::new(0, 3), UVec2::new(3, 7)));
+        let r = URect::from_center_size(UVec2::ZERO, UVec2::new(3, 3));
+        assert_eq!(r, URect::from_corners(UVec2::ZERO, UVec2::new(2, 2)));
+
+        let r = URect::from_center_size(UVec2::splat(u32::MAX - 1), UVec2::splat(4));
+        assert_eq!(r.max, UVec2::splat(u32::MAX));
+
+        // signed rects go below zero as expected
+        let r = IRect::from_center_size(IVec2::new(1, 5), IVec2::new(4, 4));
+        assert_eq!(r, IRect::from_corners(IVec2::new(-1, 3), IVec2::new(3, 7)));
+    }
+
+    #[test]
+    fn rect_urect_conversions() {
+        // rounds outwards, and clamps at zero
+        let r = rect(-0.5, 1.2, 3.5, 4.0);
+        assert_eq!(
+            r.as_urect(),
+            URect::from_corners(UVec2::new(0, 1), UVec2::new(4, 4))
+        );
+
+        let u = URect::from_corners(UVec2::new(1, 2), UVec2::new(3, 4));
+        assert_eq!(u.as_rect(), rect(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(u.as_rect().as_urect(), u);
+    }
+
+    #[test]
+    fn rect_normalize() {
+        // a selection box dragged from the bottom-right to the top-left
+        let selection = Rect {
+            min: Vec2::new(10.0, 10.0),
+            max: Vec2::new(2.0, 4.0),
+        };
+        assert_eq!(selection.size(), Vec2::new(-8.0, -6.0));
+
+        let normalized = selection.normalize();
+        assert_eq!(normalized, rect(2.0, 4.0, 10.0, 10.0));
+        assert_eq!(normalized.size(), Vec2::new(8.0, 6.0));
+        assert_eq!(normalized.half_size(), Vec2::new(4.0, 3.0));
+        assert_eq!(normalized.half_extents(), normalized.half_size());
+        assert_eq!(normalized.center(), selection.center());
+        assert!(normalized.contains(Vec2::new(5.0, 5.0)));
+        assert!(normalized.contains(Vec2::new(10.0, 4.0)));
+        assert!(!normalized.contains(Vec2::new(1.0, 5.0)));
+    }
+
+    #[test]
+    fn rect_irect_conversions() {
+        // a float rect maps to the integer rect containing every pixel it touches
+        let r = rect(-1.5, 0.25, 2.0, 3.75);
+        assert_eq!(
+            r.as_irect(),
+            IRect::from_corners(IVec2::new(-2, 0), IVec2::new(2, 4))
+        );
+        assert!(r.as_irect().as_rect().contains_rect(r));
+
+        let i = IRect::from_corners(IVec2::new(-3, -2), IVec2::new(1, 4));
+        assert_eq!(i.as_rect(), rect(-3.0, -2.0, 1.0, 4.0));
+        assert_eq!(i.as_rect().as_irect(), i);
+    }
+
+    #[test]
+    fn rect_from_center_half_size() {
+        let r = Rect::from_center_half_size(Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0));
+        assert_eq!(r, rect(-2.0, -2.0, 4.0, 6.0));
+        assert_eq!(r.width(), 6.0);
+        assert_eq!(r.height(), 8.0);
+        assert_eq!(r.half_size(), Vec2::new(3.0, 4.0));
+        assert_eq!(r.center(), Vec2::new(1.0, 2.0));
+        assert_eq!(Rect::from_center_size(r.center(), r.size()), r);
+
+        // a zero size rect still has a well defined center
+        let point = Rect::from_center_size(Vec2::new(5.0, -3.0), Vec2::ZERO);
+        assert!(point.is_empty());
+        assert_eq!(point.size(), Vec2::ZERO);
+        assert_eq!(point.center(), Vec2::new(5.0, -3.0));
+    }
+
+    #[test]
+    fn rect_lerp() {
+        let a = rect(0.1, -0.3, 0.7, 1.9);
+        let b = rect(-3.3, 2.2, 5.1, 8.6);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(
+            rect(0.0, 0.0, 2.0, 2.0).lerp(rect(4.0, 2.0, 10.0, 4.0), 0.5),
+            rect(2.0, 1.0, 6.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn rect_scaled_about() {
+        let r = rect(2.0, 4.0, 6.0, 10.0);
+        let pivot = r.center();
+        let grown = r.scaled_about(pivot, Vec2::new(2.0, 0.5));
+        assert_eq!(grown, rect(0.0, 5.5, 8.0, 8.5));
+        assert_eq!(grown.center(), pivot);
+
+        // the pivot stays in place whatever the factor
+        let r = rect(0.3, -1.7, 2.9, 0.6);
+        for &factor in [0.25, 0.9, 1.7, 3.0].iter() {
+            let scaled = r.scaled_about(r.center(), Vec2::splat(factor));
+            assert!(scaled.center().abs_diff_eq(r.center(), 1e-6));
+            assert!(scaled.size().abs_diff_eq(r.size() * factor, 1e-5));
+        }
+
+        // scaling about a corner keeps that corner fixed
+        let scaled = r.scaled_about(r.min, Vec2::splat(3.0));
+        assert_eq!(scaled.min, r.min);
+        assert!(scaled.size().abs_diff_eq(r.size() * 3.0, 1e-5));
+
+        // a negative factor mirrors the rect around the pivot
+        let mirrored = rect(1.0, 1.0, 3.0, 2.0).scaled_about(Vec2::ZERO, Vec2::new(-1.0, 1.0));
+        assert_eq!(mirrored.normalize(), rect(-3.0, 1.0, -1.0, 2.0));
+    }
 }
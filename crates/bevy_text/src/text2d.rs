@@ -9,9 +9,10 @@ use bevy_math::{Size, Vec3};
 use bevy_render::{
     draw::{DrawContext, Drawable, OutsideFrustum},
     mesh::Mesh,
-    prelude::{Draw, Msaa, Texture, Visible},
+    prelude::{ComputedVisibility, Draw, Msaa, Texture, Visible},
     render_graph::base::MainPass,
     renderer::RenderResourceBindings,
+    visibility,
 };
 use bevy_sprite::{TextureAtlas, QUAD_HANDLE};
 use bevy_transform::prelude::{GlobalTransform, Transform};
@@ -26,6 +27,7 @@ use crate::{DefaultTextPipeline, DrawableText, Font, FontAtlasSet, Text, Text2dS
 pub struct Text2dBundle {
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub text: Text,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
@@ -44,6 +46,7 @@ impl Default for Text2dBundle {
                 ..Default::default()
             },
             text: Default::default(),
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
             main_pass: MainPass {},
@@ -70,6 +73,7 @@ pub fn draw_text2d_system(
             Entity,
             &mut Draw,
             &Visible,
+            Option<&ComputedVisibility>,
             &Text,
             &GlobalTransform,
             &Text2dSize,
@@ -86,8 +90,10 @@ pub fn draw_text2d_system(
         1.
     };
 
-    for (entity, mut draw, visible, text, global_transform, calculated_size) in query.iter_mut() {
-        if !visible.is_visible {
+    for (entity, mut draw, visible, computed_visibility, text, global_transform, calculated_size) in
+        query.iter_mut()
+    {
+        if !visibility::is_visible(visible, computed_visibility) {
             continue;
         }
 
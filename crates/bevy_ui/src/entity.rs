@@ -11,7 +11,7 @@ use bevy_render::{
     draw::Draw,
     mesh::Mesh,
     pipeline::{RenderPipeline, RenderPipelines},
-    prelude::Visible,
+    prelude::{ComputedVisibility, Visible},
 };
 use bevy_sprite::{ColorMaterial, QUAD_HANDLE};
 use bevy_text::Text;
@@ -25,6 +25,7 @@ pub struct NodeBundle {
     pub material: Handle<ColorMaterial>,
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
@@ -45,6 +46,7 @@ impl Default for NodeBundle {
             style: Default::default(),
             material: Default::default(),
             draw: Default::default(),
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -61,6 +63,7 @@ pub struct ImageBundle {
     pub material: Handle<ColorMaterial>,
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
@@ -83,6 +86,7 @@ impl Default for ImageBundle {
                 is_transparent: true,
                 ..Default::default()
             },
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -95,6 +99,7 @@ pub struct TextBundle {
     pub style: Style,
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub text: Text,
     pub calculated_size: CalculatedSize,
     pub focus_policy: FocusPolicy,
@@ -117,6 +122,7 @@ impl Default for TextBundle {
             node: Default::default(),
             calculated_size: Default::default(),
             style: Default::default(),
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -134,6 +140,7 @@ pub struct ButtonBundle {
     pub material: Handle<ColorMaterial>,
     pub draw: Draw,
     pub visible: Visible,
+    pub computed_visibility: ComputedVisibility,
     pub render_pipelines: RenderPipelines,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
@@ -157,6 +164,7 @@ impl Default for ButtonBundle {
                 is_transparent: true,
                 ..Default::default()
             },
+            computed_visibility: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
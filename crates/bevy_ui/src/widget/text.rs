@@ -9,9 +9,10 @@ use bevy_math::Size;
 use bevy_render::{
     draw::{Draw, DrawContext, Drawable, OutsideFrustum},
     mesh::Mesh,
-    prelude::{Msaa, Visible},
+    prelude::{ComputedVisibility, Msaa, Visible},
     renderer::RenderResourceBindings,
     texture::Texture,
+    visibility,
 };
 use bevy_sprite::{TextureAtlas, QUAD_HANDLE};
 use bevy_text::{DefaultTextPipeline, DrawableText, Font, FontAtlasSet, Text, TextError};
@@ -148,7 +149,15 @@ pub fn draw_text_system(
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
     text_pipeline: Res<DefaultTextPipeline>,
     mut query: Query<
-        (Entity, &mut Draw, &Visible, &Text, &Node, &GlobalTransform),
+        (
+            Entity,
+            &mut Draw,
+            &Visible,
+            Option<&ComputedVisibility>,
+            &Text,
+            &Node,
+            &GlobalTransform,
+        ),
         Without<OutsideFrustum>,
     >,
 ) {
@@ -161,8 +170,10 @@ pub fn draw_text_system(
     let font_quad = meshes.get(&QUAD_HANDLE).unwrap();
     let vertex_buffer_layout = font_quad.get_vertex_buffer_layout();
 
-    for (entity, mut draw, visible, text, node, global_transform) in query.iter_mut() {
-        if !visible.is_visible {
+    for (entity, mut draw, visible, computed_visibility, text, node, global_transform) in
+        query.iter_mut()
+    {
+        if !visibility::is_visible(visible, computed_visibility) {
             continue;
         }
 